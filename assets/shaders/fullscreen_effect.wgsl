@@ -7,6 +7,7 @@ struct FeverPostProcessSettings {
     fever: f32,
     damage_threshold: f32,
     damage_indicator: f32,
+    hallucination: f32,
 }
 
 @group(0) @binding(0) var screen_texture: texture_2d<f32>;
@@ -27,7 +28,8 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
 
     let time = globals.time;
     let resolution = settings.resolution;
-    let fever = clamp(settings.fever + settings.damage_indicator, 0.0, 1.0);
+    let hallucination = clamp(settings.hallucination, 0.0, 1.0);
+    let fever = clamp(settings.fever + settings.damage_indicator + hallucination, 0.0, 1.0);
 
     // Motion setup
     let motion = textureLoad(motion_texture, coords, 0).xy;
@@ -73,9 +75,9 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
     let value = fract(f32(e) * 0.000003);
     let kaleidoscope = hsv2rgb(vec3<f32>(value + time * 0.6, 0.6, 0.6));
 
-    // Tint
+    // Tint, hallucinations keep their colors
     let tint = vec3<f32>(1.0, 0.0, 0.0);
-    let pattern = mix(kaleidoscope, tint, 0.6);
+    let pattern = mix(kaleidoscope, tint, 0.6 * (1.0 - hallucination));
 
     // Vignette
     let dist = distance(in.uv, vec2<f32>(0.5));
@@ -0,0 +1,29 @@
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+struct Progress {
+    value: f32,
+    thickness: f32,
+    _padding: vec2<f32>,
+};
+
+@group(1) @binding(0) var<uniform> color: vec4<f32>;
+@group(1) @binding(1) var<uniform> track_color: vec4<f32>;
+@group(1) @binding(2) var<uniform> progress: Progress;
+
+const TAU: f32 = 6.28318530718;
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    let p = in.uv - vec2(0.5);
+    // 0 at the outer edge of the ring, negative inside it
+    let ring_distance = abs(length(p) - (0.5 - 0.5 * progress.thickness)) - 0.5 * progress.thickness;
+    let aa = fwidth(ring_distance);
+    let ring = 1.0 - smoothstep(-aa, aa, ring_distance);
+
+    // Fill clockwise, starting at the top
+    let angle = fract(atan2(p.x, -p.y) / TAU + 1.0);
+    let filled = step(angle, progress.value);
+
+    let ring_color = mix(track_color, color, filled);
+    return vec4(ring_color.rgb, ring_color.a * ring);
+}
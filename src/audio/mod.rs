@@ -25,7 +25,8 @@ pub(super) fn plugin(app: &mut App) {
 	.add_systems(OnEnter(Menu::Pause), enable_cutoff_filter)
 	.add_systems(
 		OnExit(Menu::Pause),
-		disable_cutoff_filter.run_if(not(in_state(Menu::Settings))),
+		disable_cutoff_filter
+			.run_if(not(in_state(Menu::Settings)).and(not(in_state(Menu::Journal)))),
 	);
 }
 
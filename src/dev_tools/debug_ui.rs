@@ -2,10 +2,12 @@
 
 use std::any::Any as _;
 
+use super::heat_grid::HeatGridGizmos;
 use super::input::{ForceFreeCursor, ToggleDebugUi};
 use crate::RenderLayer;
 use crate::gameplay::crosshair::CrosshairState;
 use crate::gameplay::level::LevelOneAssets;
+use crate::gameplay::yarn_variables::YarnVariables;
 use crate::{PostPhysicsAppSystems, theme::widget};
 use avian3d::prelude::*;
 use bevy::camera::visibility::RenderLayers;
@@ -19,7 +21,10 @@ use bevy_enhanced_input::prelude::*;
 use bevy_feronia::prelude::{
 	ChunkDebugConfig, HeightMapDebugConfig, ScatterOccupancyMapDebugConfig,
 };
-use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::WorldInspectorPlugin};
+use bevy_inspector_egui::{
+	bevy_egui::EguiPlugin,
+	quick::{ResourceInspectorPlugin, WorldInspectorPlugin},
+};
 use bevy_landmass::debug::{EnableLandmassDebug, Landmass3dDebugPlugin, LandmassGizmos};
 use bevy_rerecast::debug::{DetailNavmeshGizmo, NavmeshGizmoConfig};
 
@@ -39,6 +44,7 @@ pub(super) fn plugin(app: &mut App) {
 	app.add_plugins((
 		EguiPlugin::default(),
 		WorldInspectorPlugin::new().run_if(is_inspector_active),
+		ResourceInspectorPlugin::<YarnVariables>::new().run_if(is_inspector_active),
 	));
 
 	app.add_plugins((
@@ -107,6 +113,7 @@ pub(super) fn plugin(app: &mut App) {
 			toggle_physics_debug_ui.run_if(toggled_state(DebugState::Physics)),
 			toggle_landmass_debug_ui.run_if(toggled_state(DebugState::Landmass)),
 			toggle_scatter_debug_ui.run_if(toggled_state(DebugState::Scatter)),
+			toggle_heat_debug_ui.run_if(toggled_state(DebugState::Heat)),
 		)
 			.chain()
 			.in_set(PostPhysicsAppSystems::ChangeUi),
@@ -156,6 +163,7 @@ fn update_debug_ui_text(
 		DebugState::Physics => "Physics",
 		DebugState::Landmass => "Landmass",
 		DebugState::Scatter => "Scatter",
+		DebugState::Heat => "Heat",
 	}
 	.to_string();
 }
@@ -186,6 +194,11 @@ fn toggle_landmass_debug_ui(
 	navmesh.detail_navmesh.enabled = !navmesh.detail_navmesh.enabled;
 }
 
+fn toggle_heat_debug_ui(mut config_store: ResMut<GizmoConfigStore>) {
+	let config = config_store.config_mut::<HeatGridGizmos>().0;
+	config.enabled = !config.enabled;
+}
+
 fn toggle_scatter_debug_ui(
 	mut cmd: Commands,
 	scatter_debug: Option<Res<ScatterOccupancyMapDebugConfig>>,
@@ -251,6 +264,7 @@ enum DebugState {
 	Physics,
 	Landmass,
 	Scatter,
+	Heat,
 }
 
 impl DebugState {
@@ -261,7 +275,8 @@ impl DebugState {
 			Self::Lighting => Self::Physics,
 			Self::Physics => Self::Landmass,
 			Self::Landmass => Self::Scatter,
-			Self::Scatter => Self::None,
+			Self::Scatter => Self::Heat,
+			Self::Heat => Self::None,
 		}
 	}
 }
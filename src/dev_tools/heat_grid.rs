@@ -0,0 +1,142 @@
+//! Overlay for the [`HeatGrid`]: draws the air temperatures around the player and probes the one being looked at.
+
+use avian3d::prelude::*;
+use bevy::{camera::visibility::RenderLayers, prelude::*};
+
+use crate::{
+	RenderLayer,
+	gameplay::{core::HeatGrid, player::camera::PlayerCameraParent},
+	theme::widget,
+	third_party::avian3d::CollisionLayer,
+};
+
+pub(super) fn plugin(app: &mut App) {
+	app.insert_gizmo_config(
+		HeatGridGizmos,
+		GizmoConfig {
+			enabled: false,
+			render_layers: RenderLayers::from(RenderLayer::GIZMO3),
+			..default()
+		},
+	);
+	app.add_systems(Startup, spawn_heat_probe_text);
+	app.add_systems(Update, (draw_heat_grid, probe_heat_grid));
+}
+
+/// Gizmos for the heat grid overlay, toggled through the debug UI
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub(super) struct HeatGridGizmos;
+
+#[derive(Component)]
+struct HeatProbe;
+
+#[derive(Component)]
+struct HeatProbeText;
+
+/// Only cells this close to the player are drawn, so the overlay stays readable
+const DRAW_RADIUS: f32 = 12.0;
+/// How far the probe reaches from the camera
+const PROBE_DISTANCE: f32 = 50.0;
+/// Temperatures at or below this are drawn blue, at or above [`HOT`] red
+const COLD: f32 = 10.0;
+const HOT: f32 = 45.0;
+
+fn spawn_heat_probe_text(mut commands: Commands) {
+	commands.spawn((
+		Name::new("Heat Probe"),
+		HeatProbe,
+		Node {
+			position_type: PositionType::Absolute,
+			bottom: Val::Px(8.0),
+			left: Val::Px(8.0),
+			..default()
+		},
+		Visibility::Hidden,
+		Pickable::IGNORE,
+		children![(widget::label(""), HeatProbeText)],
+	));
+}
+
+fn heat_color(temperature: f32) -> Color {
+	let t = ((temperature - COLD) / (HOT - COLD)).clamp(0.0, 1.0);
+	Color::srgb(t, 0.2, 1.0 - t)
+}
+
+fn draw_heat_grid(
+	config_store: Res<GizmoConfigStore>,
+	grid: Option<Res<HeatGrid>>,
+	camera: Single<&GlobalTransform, With<PlayerCameraParent>>,
+	mut gizmos: Gizmos<HeatGridGizmos>,
+) {
+	let Some(grid) = grid else {
+		return;
+	};
+	if !config_store.config::<HeatGridGizmos>().0.enabled {
+		return;
+	}
+	let size = grid.dims.as_vec3() * grid.cell_size;
+	gizmos.cuboid(
+		Transform::from_translation(grid.origin + size / 2.0).with_scale(size),
+		Color::WHITE,
+	);
+
+	let eye = camera.translation();
+	for cell in grid.cells() {
+		let center = grid.cell_center(cell);
+		if grid.is_solid(cell) || center.distance_squared(eye) > DRAW_RADIUS.powi(2) {
+			continue;
+		}
+		gizmos.sphere(
+			Isometry3d::from_translation(center),
+			grid.cell_size * 0.1,
+			heat_color(grid.temperature(cell)),
+		);
+	}
+}
+
+/// Shows the air temperature where the player is looking
+fn probe_heat_grid(
+	config_store: Res<GizmoConfigStore>,
+	grid: Option<Res<HeatGrid>>,
+	camera: Option<Single<&GlobalTransform, With<PlayerCameraParent>>>,
+	spatial: SpatialQuery,
+	mut visibility: Single<&mut Visibility, With<HeatProbe>>,
+	mut text: Single<&mut Text, With<HeatProbeText>>,
+	mut gizmos: Gizmos<HeatGridGizmos>,
+) {
+	let enabled = config_store.config::<HeatGridGizmos>().0.enabled;
+	let (Some(grid), Some(camera), true) = (grid, camera, enabled) else {
+		visibility.set_if_neq(Visibility::Hidden);
+		return;
+	};
+	visibility.set_if_neq(Visibility::Inherited);
+
+	let eye = camera.translation();
+	let forward = camera.forward();
+	let filter = SpatialQueryFilter::from_mask(CollisionLayer::Default);
+	// Probe the air just in front of whatever is being looked at
+	let distance = spatial
+		.cast_ray(eye, forward, PROBE_DISTANCE, true, &filter)
+		.map_or(PROBE_DISTANCE, |hit| hit.distance);
+	let point = eye + forward * (distance - 0.1).max(0.0);
+
+	match grid.sample(point) {
+		Some(temperature) => {
+			gizmos.sphere(
+				Isometry3d::from_translation(point),
+				0.15,
+				heat_color(temperature),
+			);
+			text.0 = format!(
+				"Heat probe: {temperature:.1} °C at {:.1}\nGrid: {} cells of {:.2} m",
+				point, grid.dims, grid.cell_size
+			);
+		}
+		None => {
+			text.0 = format!(
+				"Heat probe: outside the grid at {:.1}\nGrid: {} cells of {:.2} m",
+				point, grid.dims, grid.cell_size
+			);
+		}
+	}
+}
@@ -3,6 +3,7 @@
 use bevy::{dev_tools::states::log_transitions, prelude::*};
 
 mod debug_ui;
+mod heat_grid;
 mod input;
 pub(crate) mod log_components;
 mod validate_preloading;
@@ -23,6 +24,7 @@ pub(super) fn plugin(app: &mut App) {
 
 	app.add_plugins((
 		debug_ui::plugin,
+		heat_grid::plugin,
 		input::plugin,
 		validate_preloading::plugin,
 		log_components::plugin,
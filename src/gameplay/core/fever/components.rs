@@ -3,7 +3,16 @@ use std::time::Duration;
 /// Makes a unit affected by fever.
 #[derive(Component, Debug, Clone, Copy, Reflect)]
 #[reflect(Clone, Debug, Component)]
-#[require(Temperature, Health, FeverTimer, FeverDamage, FeverSources)]
+#[require(
+	Temperature,
+	BaseTemperature,
+	TemperatureThreshold,
+	MaxTemperature,
+	Health,
+	FeverTimer,
+	FeverDamage,
+	FeverSources
+)]
 pub struct Fever;
 
 /// Marker component for units that are currently feverish (temp higher than base temp).
@@ -2,6 +2,9 @@ use avian3d::prelude::*;
 use bevy::prelude::*;
 
 /// Marker component for a temperature sensor, e.g., inserted as a child on the player character controller.
+///
+/// Makes the entity it belongs to take on the temperature of its surroundings, see [`temp`](super::systems::temp).
+/// That's the sensor entity itself if it has a [`Temperature`] or is a rigid body, otherwise its parent.
 #[derive(Component, Debug, Clone, Copy, Reflect)]
 #[reflect(Clone, Debug, Component)]
 #[require(Collider::sphere(2.0), Sensor, CollidingEntities::default())]
@@ -10,7 +13,6 @@ pub struct TemperatureSensor;
 /// Current temperature of an entity
 #[derive(Component, Debug, Deref, DerefMut, Clone, Copy, Reflect)]
 #[reflect(Clone, Debug, Component)]
-#[require(MaxTemperature, BaseTemperature, TemperatureThreshold)]
 pub struct Temperature(pub f32);
 
 impl Default for Temperature {
@@ -21,6 +23,8 @@ impl Default for Temperature {
 
 /// Base temperature of an entity for simulating Homeostasis,
 /// i.e., it's the minimum temperature that an entity can reach.
+///
+/// Entities without one, like props, cool down as far as their surroundings let them.
 #[derive(Component, Debug, Deref, DerefMut, Clone, Copy, Reflect)]
 #[reflect(Clone, Debug, Component)]
 pub struct BaseTemperature(pub f32);
@@ -77,8 +81,8 @@ impl Default for Conductivity {
 
 /// Controls how sensitive to penetration depth the temperature transfer system is.
 ///
-/// Affects collisions with the heat sensor (multiplied by penetration depth),
-/// as well as eaten entities (flat value, defaults to `10.`).
+/// Affects collisions with the heat sensor (multiplied by penetration depth, defaults to `10.`).
+/// Eaten and held entities exchange heat with the player through [`Conductivity`] instead.
 ///
 /// Might have to tweak this to use higher env temperatures, other temperature scales or a larger sensor.
 #[derive(Component, Debug, Deref, DerefMut, Clone, Reflect)]
@@ -0,0 +1,331 @@
+//! A coarse 3D grid of air temperatures covering the level.
+//!
+//! The grid starts out at the [`GlobalTemperature`] and is heated or cooled by [`EnvironmentTemperature`] sources.
+//! Heat diffuses between neighboring cells, but barely through level geometry, so walls insulate rooms from each other.
+//! [`temp`](super::systems::temp) samples the grid at each [`TemperatureSensor`](super::TemperatureSensor).
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use super::{EnvironmentTemperature, GlobalTemperature};
+use crate::{gameplay::level::Level, third_party::avian3d::CollisionLayer};
+
+/// Larger levels get larger cells instead of more of them
+const MAX_CELLS: usize = 16_384;
+const MIN_CELL_SIZE: f32 = 1.0;
+/// How fast heat spreads through open air, in square meters per second
+const DIFFUSIVITY: f32 = 1.0;
+/// How much heat still flows between two cells with level geometry between them, compared to open air
+const WALL_CONDUCTANCE: f32 = 0.05;
+/// How fast a heat source pulls the air around it to its temperature, per second
+const SOURCE_RATE: f32 = 2.0;
+/// How fast the air returns to the global temperature on its own, per second
+const AMBIENT_RATE: f32 = 0.02;
+
+/// Air temperatures of the current level, in cubic cells
+#[derive(Resource, Debug, Clone)]
+pub struct HeatGrid {
+	/// The corner of the grid with the lowest coordinates
+	pub origin: Vec3,
+	/// The length of a cell's edge, in meters
+	pub cell_size: f32,
+	/// How many cells the grid has along each axis
+	pub dims: UVec3,
+	temperatures: Vec<f32>,
+	/// Cells inside level geometry, which don't take part in the diffusion
+	solid: Vec<bool>,
+	/// How well heat flows from a cell to its neighbors along +X, +Y and +Z,
+	/// from `1` for open air down to [`WALL_CONDUCTANCE`]
+	conductance: Vec<[f32; 3]>,
+}
+
+impl HeatGrid {
+	/// An open grid spanning `min` to `max` at a uniform `temperature`, with cells as small as [`MAX_CELLS`] allows
+	pub fn new(min: Vec3, max: Vec3, temperature: f32) -> Self {
+		let size = (max - min).max(Vec3::splat(MIN_CELL_SIZE));
+		let cell_size = (size.element_product() / MAX_CELLS as f32)
+			.cbrt()
+			.max(MIN_CELL_SIZE);
+		let dims = (size / cell_size).ceil().as_uvec3().max(UVec3::ONE);
+		let len = dims.element_product() as usize;
+		Self {
+			origin: min,
+			cell_size,
+			dims,
+			temperatures: vec![temperature; len],
+			solid: vec![false; len],
+			conductance: vec![[1.0; 3]; len],
+		}
+	}
+
+	fn index(&self, cell: UVec3) -> usize {
+		(cell.x + self.dims.x * (cell.y + self.dims.y * cell.z)) as usize
+	}
+
+	pub fn cells(&self) -> impl Iterator<Item = UVec3> + use<> {
+		let dims = self.dims;
+		(0..dims.z).flat_map(move |z| {
+			(0..dims.y).flat_map(move |y| (0..dims.x).map(move |x| UVec3::new(x, y, z)))
+		})
+	}
+
+	pub fn cell_center(&self, cell: UVec3) -> Vec3 {
+		self.origin + (cell.as_vec3() + 0.5) * self.cell_size
+	}
+
+	/// The cell containing `point`, if it's within the grid
+	pub fn cell_at(&self, point: Vec3) -> Option<UVec3> {
+		let cell = ((point - self.origin) / self.cell_size).floor();
+		(cell.cmpge(Vec3::ZERO).all() && cell.cmplt(self.dims.as_vec3()).all())
+			.then(|| cell.as_uvec3())
+	}
+
+	pub fn temperature(&self, cell: UVec3) -> f32 {
+		self.temperatures[self.index(cell)]
+	}
+
+	pub fn is_solid(&self, cell: UVec3) -> bool {
+		self.solid[self.index(cell)]
+	}
+
+	/// Marks a cell as inside level geometry
+	pub fn set_solid(&mut self, cell: UVec3) {
+		let index = self.index(cell);
+		self.solid[index] = true;
+	}
+
+	/// Insulates a cell from its neighbor along the positive `axis`, `0` being X
+	pub fn set_wall(&mut self, cell: UVec3, axis: usize) {
+		let index = self.index(cell);
+		self.conductance[index][axis] = WALL_CONDUCTANCE;
+	}
+
+	/// Moves the temperature of an open cell towards `temperature`, by `amount` from `0` for not at all to `1` for all the way
+	pub fn pull_towards(&mut self, cell: UVec3, temperature: f32, amount: f32) {
+		let index = self.index(cell);
+		if !self.solid[index] {
+			let current = &mut self.temperatures[index];
+			*current += (temperature - *current) * amount.clamp(0.0, 1.0);
+		}
+	}
+
+	/// The air temperature at `point`, interpolated between the open cells around it.
+	/// `None` outside the grid or deep inside level geometry.
+	pub fn sample(&self, point: Vec3) -> Option<f32> {
+		self.cell_at(point)?;
+		// Relative to the cell centers
+		let local = (point - self.origin) / self.cell_size - 0.5;
+		let base = local.floor();
+		let fraction = local - base;
+		let max_cell = self.dims.as_ivec3() - 1;
+
+		let (mut sum, mut total_weight) = (0.0, 0.0);
+		for corner in 0..8 {
+			let offset = UVec3::new(corner & 1, (corner >> 1) & 1, (corner >> 2) & 1).as_vec3();
+			let cell = (base + offset)
+				.as_ivec3()
+				.clamp(IVec3::ZERO, max_cell)
+				.as_uvec3();
+			let index = self.index(cell);
+			if self.solid[index] {
+				continue;
+			}
+			let weight = (offset * fraction + (Vec3::ONE - offset) * (Vec3::ONE - fraction))
+				.element_product();
+			sum += weight * self.temperatures[index];
+			total_weight += weight;
+		}
+		(total_weight > 0.0).then(|| sum / total_weight)
+	}
+
+	/// Spreads heat between neighboring cells for `dt` seconds, while slowly returning to `ambient`
+	pub fn diffuse(&mut self, dt: f32, ambient: f32) {
+		// Explicit integration is only stable while less than a sixth of a cell's difference to its neighbors flows per step
+		let max_step = self.cell_size.powi(2) / (6.0 * DIFFUSIVITY);
+		let steps = (dt / max_step).ceil().max(1.0) as usize;
+		let step = dt / steps as f32;
+		let rate = DIFFUSIVITY * step / self.cell_size.powi(2);
+		let ambient_rate = (AMBIENT_RATE * step).min(1.0);
+
+		let mut next = self.temperatures.clone();
+		for _ in 0..steps {
+			next.copy_from_slice(&self.temperatures);
+			for cell in self.cells() {
+				let index = self.index(cell);
+				if self.solid[index] {
+					continue;
+				}
+				let temperature = self.temperatures[index];
+				for (axis, offset) in UVec3::AXES.into_iter().enumerate() {
+					let neighbor = cell + offset;
+					if neighbor[axis] >= self.dims[axis] {
+						continue;
+					}
+					let neighbor = self.index(neighbor);
+					if self.solid[neighbor] {
+						continue;
+					}
+					let flow = rate
+						* self.conductance[index][axis]
+						* (self.temperatures[neighbor] - temperature);
+					next[index] += flow;
+					next[neighbor] -= flow;
+				}
+				next[index] += (ambient - temperature) * ambient_rate;
+			}
+			std::mem::swap(&mut self.temperatures, &mut next);
+		}
+	}
+}
+
+/// Builds the grid once the level's colliders are in place.
+/// Cells inside static geometry are solid, and static geometry between two cells insulates them from each other.
+pub fn build_heat_grid(
+	levels: Query<Entity, With<Level>>,
+	children: Query<&Children>,
+	aabbs: Query<&ColliderAabb>,
+	colliders: Query<&ColliderOf>,
+	bodies: Query<&RigidBody>,
+	global_temp: Res<GlobalTemperature>,
+	spatial: SpatialQuery,
+	mut commands: Commands,
+) {
+	let Some(bounds) = levels
+		.iter()
+		.flat_map(|level| children.iter_descendants(level))
+		.filter_map(|entity| aabbs.get(entity).ok())
+		// Half-spaces and the like reach out forever
+		.filter(|aabb| aabb.min.is_finite() && aabb.max.is_finite())
+		.copied()
+		.reduce(ColliderAabb::merged)
+	else {
+		return;
+	};
+	// The colliders exist, but haven't been placed by the physics step yet
+	if bounds.size().max_element() < MIN_CELL_SIZE {
+		return;
+	}
+
+	let is_static = |entity: Entity| {
+		colliders
+			.get(entity)
+			.is_ok_and(|collider_of| bodies.get(collider_of.body).is_ok_and(RigidBody::is_static))
+	};
+	let filter = SpatialQueryFilter::from_mask(CollisionLayer::Default);
+	let mut grid = HeatGrid::new(bounds.min, bounds.max, **global_temp);
+	for cell in grid.cells() {
+		let center = grid.cell_center(cell);
+		if spatial
+			.point_intersections(center, &filter)
+			.into_iter()
+			.any(is_static)
+		{
+			grid.set_solid(cell);
+		}
+	}
+	for cell in grid.cells() {
+		let center = grid.cell_center(cell);
+		for (axis, direction) in [Dir3::X, Dir3::Y, Dir3::Z].into_iter().enumerate() {
+			let hit = spatial.cast_ray_predicate(
+				center,
+				direction,
+				grid.cell_size,
+				true,
+				&filter,
+				&is_static,
+			);
+			if hit.is_some() {
+				grid.set_wall(cell, axis);
+			}
+		}
+	}
+	info!(
+		"Built a {} heat grid with {} m cells",
+		grid.dims, grid.cell_size
+	);
+	commands.insert_resource(grid);
+}
+
+pub fn remove_heat_grid(mut commands: Commands) {
+	commands.remove_resource::<HeatGrid>();
+}
+
+/// Heat sources pull the air around them to their own temperature
+pub fn heat_grid_sources(
+	mut grid: ResMut<HeatGrid>,
+	sources: Query<(&EnvironmentTemperature, &GlobalTransform)>,
+	time: Res<Time>,
+) {
+	let amount = SOURCE_RATE * time.delta_secs();
+	for (temperature, transform) in &sources {
+		if let Some(cell) = grid.cell_at(transform.translation()) {
+			grid.pull_towards(cell, **temperature, amount);
+		}
+	}
+}
+
+pub fn diffuse_heat(
+	mut grid: ResMut<HeatGrid>,
+	global_temp: Res<GlobalTemperature>,
+	time: Res<Time>,
+) {
+	grid.diffuse(time.delta_secs(), **global_temp);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn corridor() -> HeatGrid {
+		HeatGrid::new(Vec3::ZERO, Vec3::new(5.0, 1.0, 1.0), 20.0)
+	}
+
+	fn heat_for(grid: &mut HeatGrid, seconds: f32) {
+		for _ in 0..(seconds * 64.0) as usize {
+			grid.pull_towards(UVec3::ZERO, 100.0, 1.0);
+			grid.diffuse(1.0 / 64.0, 20.0);
+		}
+	}
+
+	#[test]
+	fn heat_spreads_through_open_air() {
+		let mut grid = corridor();
+		assert_eq!(grid.dims, UVec3::new(5, 1, 1));
+		heat_for(&mut grid, 10.0);
+		let temperatures: Vec<f32> = grid.cells().map(|cell| grid.temperature(cell)).collect();
+		assert!(temperatures[4] > 25.0);
+		assert!(temperatures.windows(2).all(|pair| pair[0] > pair[1]));
+	}
+
+	#[test]
+	fn walls_insulate() {
+		let mut open = corridor();
+		let mut walled = corridor();
+		walled.set_wall(UVec3::new(1, 0, 0), 0);
+		heat_for(&mut open, 10.0);
+		heat_for(&mut walled, 10.0);
+		let far = UVec3::new(4, 0, 0);
+		assert!(walled.temperature(far) < open.temperature(far) - 5.0);
+	}
+
+	#[test]
+	fn solid_cells_stay_out_of_it() {
+		let mut grid = corridor();
+		grid.set_solid(UVec3::new(2, 0, 0));
+		heat_for(&mut grid, 10.0);
+		assert_eq!(grid.temperature(UVec3::new(2, 0, 0)), 20.0);
+		assert_eq!(grid.temperature(UVec3::new(4, 0, 0)), 20.0);
+		// Sampling skips the solid cell instead of averaging it in
+		let sample = grid.sample(Vec3::new(2.0, 0.5, 0.5)).unwrap();
+		assert_eq!(sample, grid.temperature(UVec3::new(1, 0, 0)));
+	}
+
+	#[test]
+	fn sample_interpolates_between_centers() {
+		let mut grid = corridor();
+		grid.pull_towards(UVec3::new(1, 0, 0), 30.0, 1.0);
+		assert_eq!(grid.sample(Vec3::new(1.5, 0.5, 0.5)), Some(30.0));
+		assert_eq!(grid.sample(Vec3::new(1.0, 0.5, 0.5)), Some(25.0));
+		assert_eq!(grid.sample(Vec3::new(-1.0, 0.5, 0.5)), None);
+	}
+}
@@ -1,4 +1,5 @@
 pub mod components;
+pub mod grid;
 pub mod plugin;
 pub mod resources;
 pub mod systems;
@@ -6,6 +7,7 @@ pub mod systems;
 use bevy::app::App;
 
 pub use components::*;
+pub use grid::HeatGrid;
 pub use plugin::*;
 pub use resources::*;
 
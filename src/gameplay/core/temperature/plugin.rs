@@ -1,12 +1,26 @@
-use bevy::app::prelude::*;
+use bevy::prelude::*;
 
-use super::{systems::*, *};
+use super::{grid::*, systems::*, *};
+use crate::screens::Screen;
 
 pub struct TemperaturePlugin;
 
 impl Plugin for TemperaturePlugin {
 	fn build(&self, app: &mut App) {
 		app.init_resource::<GlobalTemperature>()
-			.add_systems(FixedUpdate, temp);
+			.add_systems(
+				FixedUpdate,
+				(
+					build_heat_grid
+						.run_if(in_state(Screen::Gameplay).and(not(resource_exists::<HeatGrid>))),
+					(heat_grid_sources, diffuse_heat).run_if(resource_exists::<HeatGrid>),
+					conduct_heat,
+					conduct_carried_heat,
+					temp,
+				)
+					.chain(),
+			)
+			.add_systems(OnExit(Screen::Gameplay), remove_heat_grid)
+			.add_observer(init_sensed_temperature);
 	}
 }
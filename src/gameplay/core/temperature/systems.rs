@@ -1,22 +1,61 @@
+use avian_pickup::prop::HeldProp;
 use avian3d::prelude::*;
-use bevy::prelude::*;
+use bevy::{platform::collections::HashSet, prelude::*};
 
 use crate::gameplay::core::*;
+use crate::gameplay::player::Player;
 use crate::gameplay::stomach::Stomach;
 
-/// Simulates thermal transfer by weighting global temperature with
-/// collision-based and eaten temperature sources.
+/// How fast touching bodies even out their temperatures, per second and unit of their combined [`Conductivity`].
+const CONTACT_HEAT_RATE: f32 = 0.5;
+
+/// Starts entities that sense temperature, but don't have one yet, off at the global temperature.
+/// Heat sources keep their [`EnvironmentTemperature`] and aren't simulated.
+pub fn init_sensed_temperature(
+	add: On<Add, TemperatureSensor>,
+	q_owner: Query<(
+		Has<Temperature>,
+		Has<RigidBody>,
+		Has<EnvironmentTemperature>,
+	)>,
+	q_parent: Query<&ChildOf>,
+	global_temp: Res<GlobalTemperature>,
+	mut commands: Commands,
+) {
+	let owner = match q_owner.get(add.entity) {
+		Ok((true, _, _) | (_, true, _)) => add.entity,
+		_ => match q_parent.get(add.entity) {
+			Ok(child_of) => child_of.parent(),
+			Err(_) => add.entity,
+		},
+	};
+	let Ok((has_temp, _, is_source)) = q_owner.get(owner) else {
+		return;
+	};
+	if !has_temp && !is_source {
+		commands.entity(owner).insert(Temperature(**global_temp));
+	}
+}
+
+/// Simulates thermal transfer by weighting the air temperature around the sensors with
+/// collision-based temperature sources.
+///
+/// The air temperature is sampled from the [`HeatGrid`], falling back to the global temperature outside of it.
+/// Eaten props feel the player's body instead.
 pub fn temp(
 	time: Res<Time>,
 	mut q_temp: Query<(
+		Entity,
 		&mut Temperature,
-		&BaseTemperature,
-		&Children,
+		Option<&BaseTemperature>,
+		Option<&Children>,
 		Option<&Conductivity>,
 		Option<&DepthSensitivity>,
+		Has<Player>,
 	)>,
 	global_temp: Res<GlobalTemperature>,
-	q_sensor: Query<&CollidingEntities, With<TemperatureSensor>>,
+	heat_grid: Option<Res<HeatGrid>>,
+	q_sensor: Query<(&CollidingEntities, &GlobalTransform), With<TemperatureSensor>>,
 	q_collider: Query<&ColliderOf>,
 	q_env_temp: Query<&EnvironmentTemperature>,
 	collisions: Collisions,
@@ -24,19 +63,47 @@ pub fn temp(
 ) {
 	let delta_secs = time.delta_secs();
 
-	for (mut temp, temp_base, sensors, conductivity, depth_sens) in &mut q_temp {
+	let player_temp = q_temp
+		.iter()
+		.find(|(.., is_player)| *is_player)
+		.map(|(_, temp, ..)| **temp);
+
+	for (entity, mut temp, temp_base, children, conductivity, depth_sens, _) in &mut q_temp {
+		let sensors: Vec<Entity> = std::iter::once(entity)
+			.chain(children.into_iter().flatten().copied())
+			.filter(|sensor| q_sensor.contains(*sensor))
+			.collect();
+		// E.g. fever sources, which only have a temperature to be multiplied with
+		if sensors.is_empty() {
+			continue;
+		}
+
 		let depth_sens = depth_sens.cloned().unwrap_or_default();
+		let eaten = stomach.contents.contains(&entity);
+		let (air_temp_sum, air_samples) = sensors
+			.iter()
+			.filter_map(|sensor| q_sensor.get(*sensor).ok())
+			.filter_map(|(_, transform)| heat_grid.as_ref()?.sample(transform.translation()))
+			.fold((0.0, 0), |(sum, count), sample| (sum + sample, count + 1));
+		let air_temp = match player_temp {
+			Some(player_temp) if eaten => player_temp,
+			_ if air_samples > 0 => air_temp_sum / air_samples as f32,
+			_ => **global_temp,
+		};
+
 		let (temp_weighted, total_weight) = sensors
 			.iter()
-			.filter_map(|sensor| Some((sensor, q_sensor.get(sensor).ok()?)))
+			.filter_map(|sensor| Some((sensor, q_sensor.get(*sensor).ok()?.0)))
 			.flat_map(|(sensor, hits)| hits.iter().map(move |hit| (sensor, hit)))
 			.filter_map(|(sensor, hit)| Some((sensor, hit, q_collider.get(*hit).ok()?.body)))
+			// A sensor overlaps the colliders of its own body
+			.filter(|(_, _, body)| *body != entity)
 			.filter_map(|(sensor, hit, body)| Some((sensor, hit, q_env_temp.get(body).ok()?)))
 			.map(|(sensor, hit, env_temp)| {
 				(
-					env_temp,
+					**env_temp,
 					collisions
-						.get(sensor, *hit)
+						.get(*sensor, *hit)
 						.and_then(|pair| pair.find_deepest_contact())
 						.map(|p| p.penetration)
 						.unwrap_or(0.0),
@@ -46,17 +113,10 @@ pub fn temp(
 				let weight = 1.0 + (penetration * *depth_sens).max(0.0);
 				(temp, weight)
 			})
-			.chain(
-				stomach
-					.contents
-					.iter()
-					.filter_map(|e| q_env_temp.get(*e).ok())
-					.map(|t| (t, *depth_sens)),
-			)
 			.fold(
-				(**global_temp, 1.0),
+				(air_temp, 1.0),
 				|(acc_temp, acc_weight), (env_temp, weight)| {
-					(acc_temp + (**env_temp * weight), acc_weight + weight)
+					(acc_temp + (env_temp * weight), acc_weight + weight)
 				},
 			);
 
@@ -72,6 +132,10 @@ pub fn temp(
 		let temp_final = **temp + (temp_env - **temp) * rate;
 
 		// Prevent the temperature from dropping too low, i.e., below body temp.
+		let Some(temp_base) = temp_base else {
+			**temp = temp_final;
+			continue;
+		};
 		let freezing = **temp < **temp_base;
 		let too_low = temp_final < **temp_base;
 		if too_low && !freezing {
@@ -81,3 +145,88 @@ pub fn temp(
 		}
 	}
 }
+
+/// Lets touching bodies even out their temperatures.
+/// The worse conductor of the two limits how fast, like resistors in series.
+pub fn conduct_heat(
+	time: Res<Time>,
+	collisions: Collisions,
+	mut q_temp: Query<(&mut Temperature, Option<&Conductivity>)>,
+) {
+	// Bodies touch through several pairs of colliders, but should only exchange heat once
+	let mut touching = HashSet::new();
+	let mut flows = Vec::new();
+	for pair in collisions
+		.iter()
+		.filter(|pair| pair.generates_constraints())
+	{
+		let (Some(body1), Some(body2)) = (pair.body1, pair.body2) else {
+			continue;
+		};
+		if body1 == body2 || !touching.insert((body1.min(body2), body1.max(body2))) {
+			continue;
+		}
+		let Ok([(temp1, k1), (temp2, k2)]) = q_temp.get_many([body1, body2]) else {
+			continue;
+		};
+		let k1 = *k1.cloned().unwrap_or_default();
+		let k2 = *k2.cloned().unwrap_or_default();
+		if k1 + k2 <= 0.0 {
+			continue;
+		}
+		let amount = (CONTACT_HEAT_RATE * k1 * k2 / (k1 + k2) * time.delta_secs()).min(0.5);
+		flows.push((body1, body2, (**temp2 - **temp1) * amount));
+	}
+
+	for (body1, body2, flow) in flows {
+		if let Ok([(mut temp1, _), (mut temp2, _)]) = q_temp.get_many_mut([body1, body2]) {
+			**temp1 += flow;
+			**temp2 -= flow;
+		}
+	}
+}
+
+/// Lets the player and what they hold or have eaten even out their temperatures, the same way [`conduct_heat`] does for touching bodies.
+/// Heat sources keep their [`EnvironmentTemperature`], so they only heat up or cool down the player.
+pub fn conduct_carried_heat(
+	time: Res<Time>,
+	player: Option<Single<(&mut Temperature, Option<&Conductivity>), With<Player>>>,
+	mut q_carried: Query<
+		(
+			Option<&mut Temperature>,
+			Option<&EnvironmentTemperature>,
+			Option<&Conductivity>,
+		),
+		Without<Player>,
+	>,
+	q_held: Query<Entity, With<HeldProp>>,
+	stomach: Single<&Stomach>,
+) {
+	let Some(player) = player else {
+		return;
+	};
+	let (mut player_temp, player_k) = player.into_inner();
+	let player_k = *player_k.cloned().unwrap_or_default();
+
+	for carried in stomach.contents.iter().copied().chain(q_held.iter()) {
+		let Ok((temp, env_temp, k)) = q_carried.get_mut(carried) else {
+			continue;
+		};
+		let carried_temp = match (&temp, env_temp) {
+			(_, Some(env_temp)) => **env_temp,
+			(Some(temp), None) => ***temp,
+			(None, None) => continue,
+		};
+		let k = *k.cloned().unwrap_or_default();
+		if player_k + k <= 0.0 {
+			continue;
+		}
+		let amount =
+			(CONTACT_HEAT_RATE * player_k * k / (player_k + k) * time.delta_secs()).min(0.5);
+		let flow = (carried_temp - **player_temp) * amount;
+		**player_temp += flow;
+		if let (Some(mut temp), None) = (temp, env_temp) {
+			**temp -= flow;
+		}
+	}
+}
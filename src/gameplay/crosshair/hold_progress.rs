@@ -0,0 +1,97 @@
+//! A ring around the crosshair that fills up while an interaction is held, see [`HoldingInteraction`].
+
+use bevy::{prelude::*, render::render_resource::AsBindGroup, shader::ShaderRef};
+
+use crate::{
+	PostPhysicsAppSystems,
+	gameplay::{crosshair::Crosshair, interaction::HoldingInteraction},
+	theme::palette::{BUTTON_TEXT, LABEL_TEXT},
+};
+
+const RADIAL_PROGRESS_SHADER_ASSET_PATH: &str = "shaders/radial_progress.wgsl";
+
+pub(super) fn plugin(app: &mut App) {
+	app.add_plugins(UiMaterialPlugin::<RadialProgressMaterial>::default());
+	app.add_observer(spawn_hold_progress);
+	app.add_systems(
+		Update,
+		update_hold_progress.in_set(PostPhysicsAppSystems::ChangeUi),
+	);
+}
+
+/// A ring that is filled clockwise from the top up to `progress`
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub(crate) struct RadialProgressMaterial {
+	/// Color of the filled part of the ring
+	#[uniform(0)]
+	color: Vec4,
+	/// Color of the part of the ring that is not filled yet
+	#[uniform(1)]
+	track_color: Vec4,
+	/// How much of the ring is filled, from `0` to `1`
+	#[uniform(2)]
+	progress: f32,
+	/// How thick the ring is, as a fraction of the node's size
+	#[uniform(2)]
+	thickness: f32,
+	#[uniform(2)]
+	_padding: Vec2,
+}
+
+impl UiMaterial for RadialProgressMaterial {
+	fn fragment_shader() -> ShaderRef {
+		RADIAL_PROGRESS_SHADER_ASSET_PATH.into()
+	}
+}
+
+/// Marker component for the ring showing the progress of the held interaction
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct HoldProgressRing;
+
+fn spawn_hold_progress(
+	add: On<Add, Crosshair>,
+	mut materials: ResMut<Assets<RadialProgressMaterial>>,
+	mut commands: Commands,
+) {
+	let material = materials.add(RadialProgressMaterial {
+		color: BUTTON_TEXT.to_srgba().to_vec4(),
+		track_color: LABEL_TEXT.with_alpha(0.25).to_srgba().to_vec4(),
+		progress: 0.0,
+		thickness: 0.15,
+		_padding: Vec2::ZERO,
+	});
+	commands.entity(add.entity).with_child((
+		Name::new("Hold Progress Ring"),
+		HoldProgressRing,
+		Node {
+			position_type: PositionType::Absolute,
+			width: Val::Px(48.0),
+			height: Val::Px(48.0),
+			display: Display::None,
+			..default()
+		},
+		MaterialNode(material),
+		Pickable::IGNORE, // NEEDED TO UNBREAK UI
+	));
+}
+
+fn update_hold_progress(
+	ring: Single<(&mut Node, &MaterialNode<RadialProgressMaterial>), With<HoldProgressRing>>,
+	holding: Option<Res<HoldingInteraction>>,
+	mut materials: ResMut<Assets<RadialProgressMaterial>>,
+) {
+	let (mut node, material) = ring.into_inner();
+	let Some(holding) = holding else {
+		if node.display != Display::None {
+			node.display = Display::None;
+		}
+		return;
+	};
+	if node.display != Display::Flex {
+		node.display = Display::Flex;
+	}
+	if let Some(material) = materials.get_mut(&material.0) {
+		material.progress = holding.timer.fraction();
+	}
+}
@@ -14,6 +14,7 @@ use bevy::{
 use std::any::{Any as _, TypeId};
 
 pub(crate) mod assets;
+mod hold_progress;
 
 pub(super) fn plugin(app: &mut App) {
 	app.add_systems(
@@ -27,7 +28,7 @@ pub(super) fn plugin(app: &mut App) {
 	);
 	app.add_systems(OnEnter(Screen::Gameplay), spawn_crosshair);
 
-	app.add_plugins(assets::plugin);
+	app.add_plugins((assets::plugin, hold_progress::plugin));
 }
 
 /// Show a crosshair for better aiming
@@ -35,6 +36,7 @@ fn spawn_crosshair(mut commands: Commands, assets: Res<AssetServer>) {
 	commands
 		.spawn((
 			Name::new("Crosshair"),
+			Crosshair,
 			Node {
 				width: Val::Percent(100.0),
 				height: Val::Percent(100.0),
@@ -55,6 +57,11 @@ fn spawn_crosshair(mut commands: Commands, assets: Res<AssetServer>) {
 		});
 }
 
+/// Marker component for the node centering the crosshair on the screen
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub(crate) struct Crosshair;
+
 #[derive(Component, Clone, Default, Reflect)]
 #[reflect(Component, Default)]
 pub(crate) struct CrosshairState {
@@ -0,0 +1,174 @@
+//! Styling for Yarn markup in dialogue lines, e.g. `[wave]`, `[shake]`, `[color=red]`, `[b]` and `[speed=0.5]`.
+
+use bevy::color::palettes::css;
+use bevy::prelude::*;
+use bevy::text::TextLayoutInfo;
+use bevy::ui::UiSystems;
+use bevy_yarnspinner::prelude::*;
+use unicode_segmentation::UnicodeSegmentation;
+
+pub(super) fn markup_plugin(app: &mut App) {
+	app.add_systems(PostUpdate, animate_glyphs.after(UiSystems::PostLayout));
+}
+
+/// How a single grapheme of a dialogue line is written and styled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct GraphemeMarkup {
+	pub(super) color: Option<Color>,
+	pub(super) bold: bool,
+	pub(super) effect: TextEffect,
+	/// Multiplier for how fast this grapheme is typed and voiced
+	pub(super) speed: f32,
+}
+
+impl Default for GraphemeMarkup {
+	fn default() -> Self {
+		Self {
+			color: None,
+			bold: false,
+			effect: TextEffect::None,
+			speed: 1.0,
+		}
+	}
+}
+
+impl GraphemeMarkup {
+	/// Whether two graphemes can share the same text span
+	pub(super) fn same_style(&self, other: &Self) -> bool {
+		self.color == other.color && self.bold == other.bold && self.effect == other.effect
+	}
+
+	fn apply(&mut self, attribute: &MarkupAttribute) {
+		match attribute.name.as_str() {
+			"wave" => self.effect = TextEffect::Wave,
+			"shake" => self.effect = TextEffect::Shake,
+			"b" => self.bold = true,
+			"color" => {
+				if let Some(MarkupValue::String(color)) = attribute.property("color") {
+					self.color = parse_color(color);
+				}
+			}
+			"speed" => {
+				if let Some(speed) = attribute.property("speed").and_then(markup_number) {
+					self.speed = speed.max(MIN_SPEED);
+				}
+			}
+			_ => {}
+		}
+	}
+}
+
+/// Animated offsets applied to glyphs after layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub(super) enum TextEffect {
+	#[default]
+	None,
+	Wave,
+	Shake,
+}
+
+/// The [`TextEffect`] of every span of the dialogue text, indexed like [`PositionedGlyph::span_index`](bevy::text::PositionedGlyph::span_index),
+/// so index 0 is the root [`Text`] itself.
+#[derive(Debug, Default, Component)]
+pub(super) struct GlyphEffects {
+	pub(super) span_effects: Vec<TextEffect>,
+	/// Offsets that were added to the glyphs the last time they were animated
+	applied: Vec<Vec2>,
+}
+
+/// Splits the line's text into graphemes, without the character name, with the markup that applies to each of them
+pub(super) fn parse_markup(line: &LocalizedLine) -> (Vec<String>, Vec<GraphemeMarkup>) {
+	let line = match line.attribute("character") {
+		Some(character) => line.delete_range(character),
+		None => line.clone(),
+	};
+	line.text
+		.grapheme_indices(true)
+		.map(|(position, grapheme)| {
+			let mut markup = GraphemeMarkup::default();
+			for attribute in &line.attributes {
+				if (attribute.position..attribute.position + attribute.length).contains(&position) {
+					markup.apply(attribute);
+				}
+			}
+			(grapheme.to_string(), markup)
+		})
+		.unzip()
+}
+
+fn markup_number(value: &MarkupValue) -> Option<f32> {
+	match value {
+		MarkupValue::Integer(value) => Some(*value as f32),
+		MarkupValue::Float(value) => Some(*value),
+		MarkupValue::String(value) => value.parse().ok(),
+		_ => None,
+	}
+}
+
+/// Accepts hex codes like `#ff8800` and a few common color names
+fn parse_color(color: &str) -> Option<Color> {
+	if let Ok(color) = Srgba::hex(color) {
+		return Some(color.into());
+	}
+	let color = match color.to_lowercase().as_str() {
+		"red" => css::RED,
+		"orange" => css::ORANGE,
+		"yellow" => css::YELLOW,
+		"green" => css::LIME,
+		"blue" => css::DEEP_SKY_BLUE,
+		"purple" => css::MEDIUM_PURPLE,
+		"pink" => css::HOT_PINK,
+		"white" => css::WHITE,
+		"gray" | "grey" => css::GRAY,
+		"black" => css::BLACK,
+		_ => return None,
+	};
+	Some(color.into())
+}
+
+fn animate_glyphs(time: Res<Time>, mut texts: Query<(&mut TextLayoutInfo, &mut GlyphEffects)>) {
+	let t = time.elapsed_secs();
+	for (mut layout, effects) in &mut texts {
+		let GlyphEffects {
+			span_effects,
+			applied,
+		} = effects.into_inner();
+		// A fresh layout has no offsets applied yet
+		let previous = if layout.is_changed() {
+			Vec::new()
+		} else {
+			std::mem::take(applied)
+		};
+		let layout = layout.bypass_change_detection();
+		let scale = layout.scale_factor;
+		*applied = layout
+			.glyphs
+			.iter_mut()
+			.enumerate()
+			.map(|(i, glyph)| {
+				let effect = span_effects
+					.get(glyph.span_index)
+					.copied()
+					.unwrap_or_default();
+				let offset = match effect {
+					TextEffect::None => Vec2::ZERO,
+					TextEffect::Wave => {
+						Vec2::Y * (t * WAVE_SPEED - i as f32 * WAVE_PHASE).sin() * WAVE_HEIGHT
+					}
+					TextEffect::Shake => {
+						Vec2::new(rand::random_range(-1.0..1.0), rand::random_range(-1.0..1.0))
+							* SHAKE_DISTANCE
+					}
+				} * scale;
+				glyph.position += offset - previous.get(i).copied().unwrap_or_default();
+				offset
+			})
+			.collect();
+	}
+}
+
+const MIN_SPEED: f32 = 0.05;
+const WAVE_SPEED: f32 = 6.0;
+const WAVE_PHASE: f32 = 0.6;
+const WAVE_HEIGHT: f32 = 3.0;
+const SHAKE_DISTANCE: f32 = 1.2;
@@ -10,7 +10,9 @@ pub use setup::UiRootNode;
 pub use updating::SpeakerChangeEvent;
 
 mod assets;
+mod markup;
 mod option_selection;
+pub mod settings;
 mod setup;
 pub mod typewriter;
 mod updating;
@@ -26,6 +28,8 @@ pub(super) fn plugin(app: &mut App) {
 		"YarnSpinnerPlugin must be added before DialogueViewPlugin"
 	);
 	app.add_plugins(assets::ui_assets_plugin)
+		.add_plugins(markup::markup_plugin)
+		.add_plugins(settings::settings_plugin)
 		.add_plugins(setup::ui_setup_plugin)
 		.add_plugins(updating::ui_updating_plugin)
 		.add_plugins(typewriter::typewriter_plugin)
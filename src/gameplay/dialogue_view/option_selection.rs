@@ -1,7 +1,11 @@
 use crate::theme::palette::LABEL_TEXT;
+use crate::third_party::bevy_yarnspinner::ConversationRunner;
 
 use super::DialogueViewSystemSet;
-use super::setup::{DialogueNode, OptionButton, OptionsNode, UiRootNode, spawn_options};
+use super::setup::{
+	DialogueNode, OptionButton, OptionTimerBar, OptionsNode, UiRootNode, spawn_option_timer,
+	spawn_options,
+};
 use super::typewriter::{Typewriter, TypewriterFinishedEvent};
 use bevy::color::palettes::css;
 use bevy::platform::collections::HashMap;
@@ -18,32 +22,56 @@ pub(super) fn option_selection_plugin(app: &mut App) {
 			select_option.run_if(
 				resource_exists::<OptionSelection>.and(any_with_component::<PrimaryWindow>),
 			),
+			time_out_options.run_if(resource_exists::<OptionSelection>),
 		)
 			.chain()
 			.after(YarnSpinnerSystemSet)
 			.in_set(DialogueViewSystemSet),
 	);
 
+	app.add_observer(select_option_on_click);
 	app.add_observer(despawn_options::<DialogueCompleted>);
 	app.add_observer(despawn_options::<HasSelectedOptionEvent>);
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Event)]
-struct HasSelectedOptionEvent;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, EntityEvent)]
+struct HasSelectedOptionEvent {
+	/// The [`DialogueRunner`] an option was selected on
+	entity: Entity,
+}
 
 #[derive(Debug, Clone, PartialEq, Default, Resource)]
 pub(super) struct OptionSelection {
 	options: Vec<DialogueOption>,
+	/// Counts down until [`Self::default_option`] is chosen for the player.
+	/// Set by tagging any option with `#timeout:<seconds>`.
+	timeout: Option<Timer>,
+	/// The option tagged with `#default`, or the first one if none are.
+	default_option: Option<OptionId>,
 }
 
 impl OptionSelection {
 	pub fn from_option_set<'a>(options: impl IntoIterator<Item = &'a DialogueOption>) -> Self {
-		let options = options
+		let options: Vec<DialogueOption> = options
 			.into_iter()
 			.filter(|o| o.is_available)
 			.cloned()
 			.collect();
-		Self { options }
+		let timeout = options
+			.iter()
+			.flat_map(|option| &option.line.metadata)
+			.find_map(|tag| tag.strip_prefix("timeout:")?.parse::<f32>().ok())
+			.map(|secs| Timer::from_seconds(secs, TimerMode::Once));
+		let default_option = options
+			.iter()
+			.find(|option| option.line.metadata.iter().any(|tag| tag == "default"))
+			.or(options.first())
+			.map(|option| option.id);
+		Self {
+			options,
+			timeout,
+			default_option,
+		}
 	}
 }
 
@@ -64,6 +92,9 @@ fn create_options(
 		**root_visibility = Visibility::Inherited;
 		let mut entity_commands = commands.entity(entity);
 		spawn_options(&mut entity_commands, &option_selection.options);
+		if option_selection.timeout.is_some() {
+			spawn_option_timer(&mut entity_commands);
+		}
 	}
 }
 
@@ -80,8 +111,8 @@ fn select_option(
 	mut commands: Commands,
 	keys: Res<ButtonInput<KeyCode>>,
 	typewriter: Res<Typewriter>,
-	mut buttons: Query<(Entity, &Interaction, &OptionButton), (With<Button>, Changed<Interaction>)>,
-	mut dialogue_runners: Query<&mut DialogueRunner>,
+	mut buttons: Query<(Entity, &Interaction), (With<OptionButton>, Changed<Interaction>)>,
+	mut dialogue_runners: Query<(Entity, &mut DialogueRunner), With<ConversationRunner>>,
 	mut text_writer: TextUiWriter,
 	option_selection: Res<OptionSelection>,
 	window: Single<Entity, With<PrimaryWindow>>,
@@ -103,36 +134,86 @@ fn select_option(
 		}
 	}
 
-	for (entity, interaction, button) in buttons.iter_mut() {
+	for (entity, interaction) in buttons.iter_mut() {
 		let (color, icon) = match *interaction {
-			Interaction::Pressed if selection.is_none() => {
-				selection = Some(button.0);
-				(LABEL_TEXT.into(), SystemCursorIcon::Default)
-			}
 			Interaction::Hovered => (Color::WHITE, SystemCursorIcon::Pointer),
 			_ => (LABEL_TEXT.into(), SystemCursorIcon::Default),
 		};
 		commands.entity(*window).insert(CursorIcon::System(icon));
 		*text_writer.color(entity, 2) = TextColor(color);
 	}
-	let has_selected_id = selection.is_some();
 	if let Some(id) = selection {
-		for mut dialogue_runner in dialogue_runners.iter_mut() {
+		for (entity, mut dialogue_runner) in dialogue_runners.iter_mut() {
 			dialogue_runner.select_option(id).unwrap();
+			commands.trigger(HasSelectedOptionEvent { entity });
 		}
 	}
-	if has_selected_id {
-		commands.trigger(HasSelectedOptionEvent);
+}
+
+/// Selects an option when it's clicked, either by the mouse or by confirming the focused option
+fn select_option_on_click(
+	click: On<Pointer<Click>>,
+	mut commands: Commands,
+	buttons: Query<&OptionButton>,
+	typewriter: Option<Res<Typewriter>>,
+	option_selection: Option<Res<OptionSelection>>,
+	mut dialogue_runners: Query<(Entity, &mut DialogueRunner), With<ConversationRunner>>,
+) {
+	let Ok(button) = buttons.get(click.entity) else {
+		return;
+	};
+	if option_selection.is_none() || !typewriter.is_some_and(|typewriter| typewriter.is_finished())
+	{
+		return;
+	}
+	for (entity, mut dialogue_runner) in dialogue_runners.iter_mut() {
+		dialogue_runner.select_option(button.0).unwrap();
+		commands.trigger(HasSelectedOptionEvent { entity });
 	}
 }
 
-fn despawn_options<T: Event>(
-	_: On<T>,
+/// Picks the default option for the player when a timed choice runs out
+fn time_out_options(
+	mut commands: Commands,
+	time: Res<Time>,
+	typewriter: Res<Typewriter>,
+	mut option_selection: ResMut<OptionSelection>,
+	mut timer_bar: Query<&mut Node, With<OptionTimerBar>>,
+	mut dialogue_runners: Query<(Entity, &mut DialogueRunner), With<ConversationRunner>>,
+) {
+	if !typewriter.is_finished() {
+		return;
+	}
+	let default_option = option_selection.default_option;
+	let Some(timer) = option_selection.timeout.as_mut() else {
+		return;
+	};
+	timer.tick(time.delta());
+	for mut node in &mut timer_bar {
+		node.width = Val::Percent(timer.fraction_remaining() * 100.0);
+	}
+	if !timer.just_finished() {
+		return;
+	}
+	if let Some(id) = default_option {
+		for (entity, mut dialogue_runner) in dialogue_runners.iter_mut() {
+			dialogue_runner.select_option(id).unwrap();
+			commands.trigger(HasSelectedOptionEvent { entity });
+		}
+	}
+}
+
+fn despawn_options<T: EntityEvent>(
+	event: On<T>,
+	conversation: Query<(), With<ConversationRunner>>,
 	mut commands: Commands,
 	options_node: Single<(Entity, &mut Node, &mut Visibility), With<OptionsNode>>,
 	mut dialogue_node_text: Single<&mut Text, With<DialogueNode>>,
 	mut root_visibility: Single<&mut Visibility, (With<UiRootNode>, Without<OptionsNode>)>,
 ) {
+	if !conversation.contains(event.event_target()) {
+		return;
+	}
 	commands.remove_resource::<OptionSelection>();
 	let (entity, mut node, mut visibility) = options_node.into_inner();
 	commands.entity(entity).despawn_related::<Children>();
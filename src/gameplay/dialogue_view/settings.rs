@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use std::time::Duration;
+
+pub(super) fn settings_plugin(app: &mut App) {
+	app.init_resource::<DialogueSettings>();
+}
+
+/// User preferences for how dialogue is presented
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource)]
+pub(crate) struct DialogueSettings {
+	/// Whether fully typed lines continue on their own after giving the player time to read them
+	pub(crate) auto_advance: bool,
+	pub(crate) text_speed: TextSpeed,
+}
+
+impl Default for DialogueSettings {
+	fn default() -> Self {
+		Self {
+			auto_advance: false,
+			text_speed: TextSpeed::Normal,
+		}
+	}
+}
+
+impl DialogueSettings {
+	/// How long a fully typed line stays up before auto-advancing, based on how long it is
+	pub(crate) fn reading_time(&self, graphemes: usize) -> Duration {
+		const BASE_SECS: f32 = 1.5;
+		const SECS_PER_GRAPHEME: f32 = 0.05;
+		const MAX_SECS: f32 = 10.0;
+		Duration::from_secs_f32((BASE_SECS + graphemes as f32 * SECS_PER_GRAPHEME).min(MAX_SECS))
+	}
+}
+
+/// How fast the typewriter writes out lines
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TextSpeed {
+	Slow,
+	Normal,
+	Fast,
+	Instant,
+}
+
+impl TextSpeed {
+	/// Graphemes written per second, or [`None`] if lines should appear all at once
+	pub(crate) fn graphemes_per_second(self) -> Option<f32> {
+		match self {
+			TextSpeed::Slow => Some(20.0),
+			TextSpeed::Normal => Some(40.0),
+			TextSpeed::Fast => Some(80.0),
+			TextSpeed::Instant => None,
+		}
+	}
+
+	pub(crate) fn faster(self) -> Self {
+		match self {
+			TextSpeed::Slow => TextSpeed::Normal,
+			TextSpeed::Normal => TextSpeed::Fast,
+			TextSpeed::Fast | TextSpeed::Instant => TextSpeed::Instant,
+		}
+	}
+
+	pub(crate) fn slower(self) -> Self {
+		match self {
+			TextSpeed::Slow | TextSpeed::Normal => TextSpeed::Slow,
+			TextSpeed::Fast => TextSpeed::Normal,
+			TextSpeed::Instant => TextSpeed::Fast,
+		}
+	}
+}
@@ -1,8 +1,10 @@
+use crate::theme::navigation::Focusable;
 use crate::theme::palette::SCREEN_BACKGROUND;
 use crate::theme::textures::{BUTTON_TEXTURE, TexturedUiMaterial};
 use crate::ui_layout::RootWidget;
 
 use super::assets::image_handle;
+use super::markup::{GlyphEffects, GraphemeMarkup};
 use bevy::ecs::system::EntityCommands;
 use bevy::prelude::*;
 use bevy_yarnspinner::prelude::*;
@@ -30,6 +32,10 @@ pub(super) struct OptionsNode;
 #[derive(Debug, Component)]
 pub(super) struct OptionButton(pub OptionId);
 
+/// Shrinks as the time to pick a timed option runs out
+#[derive(Debug, Default, Component)]
+pub(super) struct OptionTimerBar;
+
 fn setup(
 	mut commands: Commands,
 	mut materials: ResMut<Assets<TexturedUiMaterial>>,
@@ -111,6 +117,7 @@ fn setup(
 						text_style::standard(),
 						style::standard(),
 						DialogueNode,
+						GlyphEffects::default(),
 						Label,
 					));
 
@@ -158,22 +165,23 @@ fn fmt_name(name: &str) -> Name {
 	Name::new(format!("Yarn Spinner example dialogue view node: {name}"))
 }
 
+/// A span of dialogue styled by its markup. Text that isn't typed yet is still laid out, but invisible
 pub(super) fn create_dialog_text(
 	text: impl Into<String>,
-	invisible: impl Into<String>,
-) -> [(TextSpan, TextFont, TextColor); 2] {
-	[
-		(
-			TextSpan(text.into()),
-			text_style::standard().0,
-			text_style::standard().1,
-		),
-		(
-			TextSpan(invisible.into()),
-			text_style::standard().0,
-			TextColor(Color::NONE),
-		),
-	]
+	markup: &GraphemeMarkup,
+	visible: bool,
+) -> (TextSpan, TextFont, TextColor) {
+	let (mut font, mut color) = text_style::standard();
+	if markup.bold {
+		font = text_style::bold().0;
+	}
+	if let Some(markup_color) = markup.color {
+		color = TextColor(markup_color);
+	}
+	if !visible {
+		color = TextColor(Color::NONE);
+	}
+	(TextSpan(text.into()), font, color)
 }
 
 pub(super) fn spawn_options<'a, T>(entity_commands: &mut EntityCommands, options: T)
@@ -196,6 +204,7 @@ where
 						.spawn((
 							fmt_name("option text"),
 							Button,
+							Focusable,
 							Text::default(),
 							style::options(),
 							ImageNode::default().with_color(Color::NONE),
@@ -215,6 +224,20 @@ where
 	});
 }
 
+pub(super) fn spawn_option_timer(entity_commands: &mut EntityCommands) {
+	entity_commands.with_child((
+		fmt_name("option timer"),
+		Node {
+			width: Val::Percent(100.0),
+			height: Val::Px(4.0),
+			margin: UiRect::top(Val::Px(10.0)),
+			..default()
+		},
+		BackgroundColor(Color::hsl(120.0, 1.0, 0.9)),
+		OptionTimerBar,
+	));
+}
+
 const DIALOG_WIDTH: f32 = 600.0;
 const TEXT_BORDER_HORIZONTAL: f32 = 60.0;
 const TEXT_BORDER_TOP: f32 = 30.0;
@@ -257,6 +280,17 @@ mod text_style {
 		)
 	}
 
+	pub(super) fn bold() -> (TextFont, TextColor) {
+		(
+			TextFont {
+				font: VARIABLE_FONT,
+				weight: FontWeight(800),
+				..standard().0
+			},
+			standard().1,
+		)
+	}
+
 	pub(super) fn name() -> (TextFont, TextColor) {
 		(
 			TextFont {
@@ -1,7 +1,10 @@
 use super::DialogueViewSystemSet;
+use super::markup::{GlyphEffects, GraphemeMarkup, TextEffect, parse_markup};
 use super::option_selection::OptionSelection;
+use super::settings::{DialogueSettings, TextSpeed};
 use super::setup::{DialogueContinueNode, DialogueNode, UiRootNode, create_dialog_text};
 use super::updating::SpeakerChangeEvent;
+use crate::third_party::bevy_yarnspinner::ConversationRunner;
 use bevy::platform::time::Instant;
 use bevy::prelude::*;
 use bevy_yarnspinner::{events::*, prelude::*};
@@ -35,6 +38,8 @@ pub(crate) struct Typewriter {
 	pub(super) current_text: String,
 	pub(super) graphemes_left: Vec<String>,
 	pub(super) last_before_options: bool,
+	/// The markup of every grapheme in the line, both written and left
+	markup: Vec<GraphemeMarkup>,
 	elapsed: f32,
 	start: Instant,
 	fast_typing: bool,
@@ -47,6 +52,7 @@ impl Default for Typewriter {
 			current_text: default(),
 			graphemes_left: default(),
 			last_before_options: default(),
+			markup: default(),
 			elapsed: default(),
 			start: Instant::now(),
 			fast_typing: default(),
@@ -56,15 +62,13 @@ impl Default for Typewriter {
 
 impl Typewriter {
 	pub(super) fn set_line(&mut self, line: &LocalizedLine) {
+		let (graphemes_left, markup) = parse_markup(line);
 		*self = Self {
 			character_name: line.character_name().map(|s| s.to_string()),
 			current_text: String::new(),
-			graphemes_left: line
-				.text_without_character_name()
-				.graphemes(true)
-				.map(|s| s.to_string())
-				.collect(),
+			graphemes_left,
 			last_before_options: line.is_last_line_before_options(),
+			markup,
 			..default()
 		};
 	}
@@ -81,22 +85,54 @@ impl Typewriter {
 		self.current_text.chars().count()
 	}
 
-	fn update_current_text(&mut self) {
+	/// How many graphemes the whole line has, including the ones not written yet
+	pub(super) fn total_graphemes(&self) -> usize {
+		self.current_text.graphemes(true).count() + self.graphemes_left.len()
+	}
+
+	fn written_graphemes(&self) -> usize {
+		self.markup.len().saturating_sub(self.graphemes_left.len())
+	}
+
+	/// The `[speed]` markup of the grapheme being typed right now
+	pub(super) fn current_speed(&self) -> f32 {
+		self.markup
+			.get(self.written_graphemes())
+			.map_or(1.0, |markup| markup.speed)
+	}
+
+	fn update_current_text(&mut self, speed: TextSpeed) {
 		if self.is_finished() {
 			return;
 		}
+		let Some(graphemes_per_second) = self.graphemes_per_second(speed) else {
+			let graphemes_to_take = self.graphemes_left.drain(..);
+			self.current_text.extend(graphemes_to_take);
+			return;
+		};
 		self.elapsed += self.start.elapsed().as_secs_f32();
 		self.start = Instant::now();
-		let calculated_graphemes = (self.graphemes_per_second() * self.elapsed).floor() as usize;
-		let graphemes_left = self.graphemes_left.len();
-		let grapheme_length_to_take = (calculated_graphemes).min(graphemes_left);
-		self.elapsed -= grapheme_length_to_take as f32 / self.graphemes_per_second();
+		let mut grapheme_length_to_take = 0;
+		for markup in &self.markup[self.written_graphemes()..] {
+			let seconds_per_grapheme = 1.0 / (graphemes_per_second * markup.speed);
+			if self.elapsed < seconds_per_grapheme {
+				break;
+			}
+			self.elapsed -= seconds_per_grapheme;
+			grapheme_length_to_take += 1;
+		}
+		let grapheme_length_to_take = grapheme_length_to_take.min(self.graphemes_left.len());
 		let graphemes_to_take = self.graphemes_left.drain(..grapheme_length_to_take);
 		self.current_text.extend(graphemes_to_take);
 	}
 
-	fn graphemes_per_second(&self) -> f32 {
-		if self.fast_typing { 120.0 } else { 40.0 }
+	fn graphemes_per_second(&self, speed: TextSpeed) -> Option<f32> {
+		let graphemes_per_second = speed.graphemes_per_second()?;
+		Some(if self.fast_typing {
+			graphemes_per_second * 3.0
+		} else {
+			graphemes_per_second
+		})
 	}
 }
 
@@ -104,9 +140,11 @@ fn write_text(
 	mut commands: Commands,
 	text: Single<Entity, With<DialogueNode>>,
 	mut typewriter: ResMut<Typewriter>,
+	settings: Res<DialogueSettings>,
 	option_selection: Option<Res<OptionSelection>>,
 	mut speaker_change_events: MessageWriter<SpeakerChangeEvent>,
 	mut root_visibility: Single<&mut Visibility, With<UiRootNode>>,
+	mut glyph_effects: Single<&mut GlyphEffects, With<DialogueNode>>,
 ) {
 	let mut text_entity = commands.entity(*text);
 	if typewriter.last_before_options && option_selection.is_none() {
@@ -120,7 +158,7 @@ fn write_text(
 		**root_visibility = Visibility::Inherited;
 		// If this is last before options, the `OptionSelection` will make the visibility inherited as soon as it's ready instead
 	}
-	typewriter.update_current_text();
+	typewriter.update_current_text(settings.text_speed);
 	if typewriter.is_finished()
 		&& let Some(name) = typewriter.character_name.as_deref()
 	{
@@ -130,14 +168,33 @@ fn write_text(
 		});
 	}
 
-	let current_text = &typewriter.current_text;
-	let rest = typewriter.graphemes_left.join("");
-	let spans = create_dialog_text(current_text, rest);
+	// Group graphemes that look the same into spans, the root `Text` itself is the first span
+	let written = typewriter.written_graphemes();
+	let graphemes = typewriter
+		.current_text
+		.graphemes(true)
+		.chain(typewriter.graphemes_left.iter().map(String::as_str));
+	let mut spans: Vec<(String, GraphemeMarkup, bool)> = Vec::new();
+	for (i, (grapheme, markup)) in graphemes.zip(&typewriter.markup).enumerate() {
+		let visible = i < written;
+		match spans.last_mut() {
+			Some((text, span_markup, span_visible))
+				if *span_visible == visible && span_markup.same_style(markup) =>
+			{
+				text.push_str(grapheme);
+			}
+			_ => spans.push((grapheme.to_string(), *markup, visible)),
+		}
+	}
+	glyph_effects.span_effects = std::iter::once(TextEffect::None)
+		.chain(spans.iter().map(|(_, markup, _)| markup.effect))
+		.collect();
 	text_entity
 		.despawn_related::<Children>()
 		.with_children(|parent| {
-			parent.spawn(spans[0].clone());
-			parent.spawn(spans[1].clone());
+			for (text, markup, visible) in spans {
+				parent.spawn(create_dialog_text(text, &markup, visible));
+			}
 		});
 }
 
@@ -153,12 +210,24 @@ fn show_continue(
 	}
 }
 
-pub(super) fn despawn(_: On<DialogueCompleted>, mut commands: Commands) {
-	commands.remove_resource::<Typewriter>();
+pub(super) fn despawn(
+	completed: On<DialogueCompleted>,
+	conversation: Query<(), With<ConversationRunner>>,
+	mut commands: Commands,
+) {
+	if conversation.contains(completed.entity) {
+		commands.remove_resource::<Typewriter>();
+	}
 }
 
-pub(super) fn spawn(_: On<DialogueStarted>, mut commands: Commands) {
-	commands.init_resource::<Typewriter>();
+pub(super) fn spawn(
+	started: On<DialogueStarted>,
+	conversation: Query<(), With<ConversationRunner>>,
+	mut commands: Commands,
+) {
+	if conversation.contains(started.entity) {
+		commands.init_resource::<Typewriter>();
+	}
 }
 
 fn bob_continue(
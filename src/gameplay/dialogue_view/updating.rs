@@ -1,8 +1,10 @@
 use super::DialogueViewSystemSet;
 use super::option_selection::OptionSelection;
+use super::settings::DialogueSettings;
 use super::setup::{DialogueContinueNode, DialogueNameNode, UiRootNode};
 use super::typewriter::Typewriter;
 use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
 use bevy_seedling::prelude::*;
 use bevy_shuffle_bag::ShuffleBag;
 use bevy_yarnspinner::{events::*, prelude::*};
@@ -11,10 +13,26 @@ use crate::asset_tracking::LoadResource;
 use crate::audio::{SfxPool, SpatialPool};
 use crate::gameplay::player::camera::PlayerCameraParent;
 use crate::gameplay::player::dialogue::DialogueSpeaker;
+use crate::theme::navigation::{ConfirmMenu, action_just_started};
+use crate::third_party::bevy_yarnspinner::ConversationRunner;
 
 #[derive(Component)]
 struct VoiceAudio;
 
+/// Gibberish that speeds up and slows down with the `[speed]` markup of the line being typed
+#[derive(Component)]
+struct GibberishVoice {
+	pitch: f64,
+}
+
+impl GibberishVoice {
+	fn random() -> Self {
+		Self {
+			pitch: rand::random_range(1.05..1.25),
+		}
+	}
+}
+
 #[derive(Resource, Asset, TypePath, Clone)]
 struct GibberishSounds(ShuffleBag<Handle<AudioSample>>);
 
@@ -44,7 +62,11 @@ pub(super) fn ui_updating_plugin(app: &mut App) {
 
 	app.add_systems(
 		Update,
-		(continue_dialogue, auto_continue_dialogue.run_if(|| false))
+		(
+			continue_dialogue,
+			pace_gibberish,
+			auto_continue_dialogue.run_if(|settings: Res<DialogueSettings>| settings.auto_advance),
+		)
 			.chain()
 			.run_if(resource_exists::<Typewriter>)
 			.after(YarnSpinnerSystemSet)
@@ -59,17 +81,14 @@ pub(super) fn ui_updating_plugin(app: &mut App) {
 	app.add_observer(present_options);
 }
 
-const AUTO_CONTINUE_DELAY_SECS: f32 = 4.0;
-
+/// Counts down the reading time of a fully typed line,
+/// its duration is set by [`present_line`] based on the line's length
 #[derive(Resource, Deref, DerefMut)]
 struct AutoContinueTimer(Timer);
 
 impl Default for AutoContinueTimer {
 	fn default() -> Self {
-		Self(Timer::from_seconds(
-			AUTO_CONTINUE_DELAY_SECS,
-			TimerMode::Once,
-		))
+		Self(Timer::from_seconds(0.0, TimerMode::Once))
 	}
 }
 
@@ -86,16 +105,26 @@ pub struct SpeakerChangeEvent {
 	pub speaking: bool,
 }
 
-fn show_dialog(_: On<DialogueStarted>, mut visibility: Single<&mut Visibility, With<UiRootNode>>) {
-	**visibility = Visibility::Inherited;
+fn show_dialog(
+	started: On<DialogueStarted>,
+	conversation: Query<(), With<ConversationRunner>>,
+	mut visibility: Single<&mut Visibility, With<UiRootNode>>,
+) {
+	if conversation.contains(started.entity) {
+		**visibility = Visibility::Inherited;
+	}
 }
 
 fn hide_dialog(
-	_: On<DialogueCompleted>,
+	completed: On<DialogueCompleted>,
+	conversation: Query<(), With<ConversationRunner>>,
 	mut root_visibility: Single<&mut Visibility, With<UiRootNode>>,
 	mut commands: Commands,
 	voice_query: Query<Entity, With<VoiceAudio>>,
 ) {
+	if !conversation.contains(completed.entity) {
+		return;
+	}
 	**root_visibility = Visibility::Hidden;
 	for entity in &voice_query {
 		commands.entity(entity).despawn();
@@ -114,9 +143,14 @@ fn present_line(
 	mut gibberish: ResMut<GibberishSounds>,
 	speaker: Res<DialogueSpeaker>,
 	transforms: Query<&GlobalTransform>,
-	runner: Single<&DialogueRunner>,
+	conversation: Query<&DialogueRunner, With<ConversationRunner>>,
 	project: Res<YarnProject>,
+	settings: Res<DialogueSettings>,
+	mut auto_continue_timer: ResMut<AutoContinueTimer>,
 ) {
+	let Ok(runner) = conversation.get(event.entity) else {
+		return;
+	};
 	// Stop any previously playing voice line.
 	for entity in &voice_query {
 		commands.entity(entity).despawn();
@@ -161,7 +195,7 @@ fn present_line(
 		if let Some(entity) = speaker.0.as_ref() {
 			commands.entity(*entity).with_child((
 				SamplePlayer::new(handle).with_volume(Volume::Decibels(2.0)),
-				RandomPitch(1.05..1.25),
+				GibberishVoice::random(),
 				SpatialPool,
 				VoiceAudio,
 				Transform::default(),
@@ -169,7 +203,7 @@ fn present_line(
 		} else {
 			commands.spawn((
 				SamplePlayer::new(handle).with_volume(Volume::Decibels(2.0)),
-				RandomPitch(1.05..1.25),
+				GibberishVoice::random(),
 				SfxPool,
 				VoiceAudio,
 				Transform::default(),
@@ -188,9 +222,18 @@ fn present_line(
 	};
 	*text_writer.text(*name_node, 0) = name;
 	typewriter.set_line(&event.line);
+	auto_continue_timer.set_duration(settings.reading_time(typewriter.total_graphemes()));
+	auto_continue_timer.reset();
 }
 
-fn present_options(event: On<PresentOptions>, mut commands: Commands) {
+fn present_options(
+	event: On<PresentOptions>,
+	conversation: Query<(), With<ConversationRunner>>,
+	mut commands: Commands,
+) {
+	if !conversation.contains(event.entity) {
+		return;
+	}
 	let option_selection = OptionSelection::from_option_set(&event.options);
 	commands.insert_resource(option_selection);
 }
@@ -199,7 +242,8 @@ fn continue_dialogue(
 	keys: Res<ButtonInput<KeyCode>>,
 	mouse_buttons: Res<ButtonInput<MouseButton>>,
 	touches: Res<Touches>,
-	mut dialogue_runners: Query<&mut DialogueRunner>,
+	confirm: Query<&ActionEvents, With<Action<ConfirmMenu>>>,
+	mut dialogue_runners: Query<&mut DialogueRunner, With<ConversationRunner>>,
 	mut typewriter: ResMut<Typewriter>,
 	option_selection: Option<Res<OptionSelection>>,
 	mut root_visibility: Single<&mut Visibility, With<UiRootNode>>,
@@ -211,7 +255,8 @@ fn continue_dialogue(
 	let explicit_continue = keys.just_pressed(KeyCode::Space)
 		|| keys.just_pressed(KeyCode::Enter)
 		|| mouse_buttons.just_pressed(MouseButton::Left)
-		|| touches.any_just_pressed();
+		|| touches.any_just_pressed()
+		|| action_just_started::<ConfirmMenu>(confirm);
 	if explicit_continue && !typewriter.is_finished() {
 		typewriter.fast_forward();
 		return;
@@ -227,11 +272,29 @@ fn continue_dialogue(
 	}
 }
 
+fn pace_gibberish(
+	typewriter: Res<Typewriter>,
+	mut voices: Query<(&GibberishVoice, &mut PlaybackSettings)>,
+) {
+	let speed = typewriter.current_speed() as f64;
+	for (voice, mut settings) in &mut voices {
+		let paced = voice.pitch * speed;
+		if settings.speed != paced {
+			settings.speed = paced;
+		}
+	}
+}
+
 fn auto_continue_dialogue(
-	mut dialogue_runners: Query<&mut DialogueRunner>,
+	mut dialogue_runners: Query<&mut DialogueRunner, With<ConversationRunner>>,
 	typewriter: Res<Typewriter>,
 	time: Res<Time>,
 	mut timer: ResMut<AutoContinueTimer>,
+	mut root_visibility: Single<&mut Visibility, With<UiRootNode>>,
+	mut continue_visibility: Single<
+		&mut Visibility,
+		(With<DialogueContinueNode>, Without<UiRootNode>),
+	>,
 ) {
 	if typewriter.is_finished() && !typewriter.last_before_options {
 		timer.tick(time.delta());
@@ -241,9 +304,10 @@ fn auto_continue_dialogue(
 					&& dialogue_runner.is_running()
 				{
 					dialogue_runner.continue_in_next_update();
+					**root_visibility = Visibility::Hidden;
+					**continue_visibility = Visibility::Hidden;
 				}
 			}
-			timer.reset();
 		}
 	} else {
 		timer.reset();
@@ -0,0 +1,331 @@
+//! The player's vitals on the HUD: a thermometer, a health bar and the fever sources heating them up.
+//!
+//! The thermometer marks the fever threshold and the max temperature, and the health bar flashes when fever deals damage.
+
+use bevy::prelude::*;
+
+use crate::{
+	PostPhysicsAppSystems,
+	gameplay::{
+		core::{fever::systems::FeverTick, *},
+		player::Player,
+	},
+	screens::Screen,
+	theme::{
+		palette::{HEADER_TEXT, LABEL_TEXT},
+		widget,
+	},
+	ui_layout::RootWidget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+	app.add_systems(OnEnter(Screen::Gameplay), spawn_vitals)
+		.add_systems(
+			Update,
+			(
+				update_thermometer,
+				update_health_bar,
+				update_fever_status,
+				fade_damage_flash,
+			)
+				.in_set(PostPhysicsAppSystems::ChangeUi),
+		)
+		.add_observer(flash_on_fever_damage);
+}
+
+/// Marker component for the part of the thermometer filled up to the player's temperature
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct ThermometerFill;
+
+/// A line on the thermometer at one of the player's temperature limits
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+enum ThermometerMarker {
+	/// Above [`TemperatureThreshold`], fever deals damage
+	Threshold,
+	/// [`MaxTemperature`]
+	Max,
+}
+
+/// Marker component for the [`Text`] showing the player's temperature
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct ThermometerText;
+
+/// Marker component for the part of the health bar filled up to the player's health
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct HealthBarFill;
+
+/// Marker component for the [`Text`] showing the player's health
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct HealthText;
+
+/// Lights up the health bar when fever deals damage, fading from `1` to `0`
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+struct DamageFlash(f32);
+
+/// Marker component for the icon that glows while the player is [`Feverish`]
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct FeverIcon;
+
+/// Marker component for the [`Text`] listing the player's [`FeverSources`]
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct FeverStatusText;
+
+const BAR_WIDTH: f32 = 220.0;
+const BAR_HEIGHT: f32 = 14.0;
+/// How many degrees the thermometer shows below the base and above the max temperature
+const THERMOMETER_MARGIN: f32 = 3.0;
+/// How long the health bar lights up after fever dealt damage
+const DAMAGE_FLASH_SECS: f32 = 0.4;
+
+const TRACK_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+const HEALTH_COLOR: Color = Color::srgb(0.75, 0.2, 0.25);
+const DAMAGE_FLASH_COLOR: Color = Color::srgb(1.0, 0.15, 0.1);
+const MAX_TEMPERATURE_COLOR: Color = Color::srgb(0.9, 0.1, 0.1);
+
+fn spawn_vitals(mut commands: Commands) {
+	commands.spawn((
+		Name::new("Vitals"),
+		Node {
+			flex_direction: FlexDirection::Column,
+			row_gap: Val::Px(6.0),
+			padding: UiRect::all(Val::Px(10.0)),
+			..default()
+		},
+		Pickable::IGNORE,
+		DespawnOnExit(Screen::Gameplay),
+		RootWidget,
+		children![
+			(
+				Name::new("Thermometer"),
+				vitals_row(),
+				children![
+					(
+						bar_track(),
+						children![
+							(bar_fill(LABEL_TEXT), ThermometerFill),
+							(marker(HEADER_TEXT), ThermometerMarker::Threshold),
+							(marker(MAX_TEMPERATURE_COLOR), ThermometerMarker::Max),
+						],
+					),
+					(widget::label_small(""), ThermometerText),
+				],
+			),
+			(
+				Name::new("Health Bar"),
+				vitals_row(),
+				children![
+					(
+						bar_track(),
+						DamageFlash::default(),
+						children![(bar_fill(HEALTH_COLOR), HealthBarFill)],
+					),
+					(widget::label_small(""), HealthText),
+				],
+			),
+			(
+				Name::new("Fever Status"),
+				vitals_row(),
+				children![
+					(
+						Name::new("Fever Icon"),
+						FeverIcon,
+						Node {
+							width: Val::Px(BAR_HEIGHT),
+							height: Val::Px(BAR_HEIGHT),
+							border_radius: BorderRadius::MAX,
+							..default()
+						},
+						BackgroundColor(TRACK_COLOR),
+					),
+					(widget::label_small(""), FeverStatusText),
+				],
+			),
+		],
+	));
+}
+
+fn vitals_row() -> impl Bundle {
+	Node {
+		align_items: AlignItems::Center,
+		column_gap: Val::Px(8.0),
+		..default()
+	}
+}
+
+fn bar_track() -> impl Bundle {
+	(
+		Node {
+			width: Val::Px(BAR_WIDTH),
+			height: Val::Px(BAR_HEIGHT),
+			..default()
+		},
+		BackgroundColor(TRACK_COLOR),
+	)
+}
+
+fn bar_fill(color: Color) -> impl Bundle {
+	(
+		Node {
+			width: Val::Percent(0.0),
+			height: Val::Percent(100.0),
+			..default()
+		},
+		BackgroundColor(color),
+	)
+}
+
+fn marker(color: Color) -> impl Bundle {
+	(
+		Node {
+			position_type: PositionType::Absolute,
+			width: Val::Px(2.0),
+			height: Val::Percent(100.0),
+			..default()
+		},
+		BackgroundColor(color),
+	)
+}
+
+/// Where `temperature` is on a thermometer spanning a little more than `base..max`, from `0` to `1`
+fn thermometer_fraction(temperature: f32, base: f32, max: f32) -> f32 {
+	let low = base - THERMOMETER_MARGIN;
+	let high = max + THERMOMETER_MARGIN;
+	((temperature - low) / (high - low).max(0.0001)).clamp(0.0, 1.0)
+}
+
+fn update_thermometer(
+	player: Option<
+		Single<
+			(
+				&Temperature,
+				&BaseTemperature,
+				&TemperatureThreshold,
+				&MaxTemperature,
+			),
+			With<Player>,
+		>,
+	>,
+	fill: Single<(&mut Node, &mut BackgroundColor), With<ThermometerFill>>,
+	mut markers: Query<(&mut Node, &ThermometerMarker), Without<ThermometerFill>>,
+	mut text: Single<&mut Text, With<ThermometerText>>,
+) {
+	let Some(player) = player else {
+		return;
+	};
+	let (temperature, base, threshold, max) = player.into_inner();
+	let (mut fill_node, mut fill_color) = fill.into_inner();
+
+	let fraction = |temperature: f32| thermometer_fraction(temperature, **base, **max) * 100.0;
+	// Only written when changed, so the UI isn't laid out again every frame
+	let width = Val::Percent(fraction(**temperature));
+	if fill_node.width != width {
+		fill_node.width = width;
+	}
+	fill_color.set_if_neq(BackgroundColor(if **temperature > **threshold {
+		HEADER_TEXT
+	} else {
+		LABEL_TEXT
+	}));
+	for (mut node, marker) in &mut markers {
+		let temperature = match marker {
+			ThermometerMarker::Threshold => **threshold,
+			ThermometerMarker::Max => **max,
+		};
+		let left = Val::Percent(fraction(temperature));
+		if node.left != left {
+			node.left = left;
+		}
+	}
+	let label = format!("{:.1} °C", **temperature);
+	if text.0 != label {
+		text.0 = label;
+	}
+}
+
+fn update_health_bar(
+	player: Option<Single<&Health, With<Player>>>,
+	mut fill: Single<&mut Node, With<HealthBarFill>>,
+	mut text: Single<&mut Text, With<HealthText>>,
+) {
+	let Some(health) = player else {
+		return;
+	};
+	let max_health = Health::default().0;
+	let width = Val::Percent((***health / max_health).clamp(0.0, 1.0) * 100.0);
+	if fill.width != width {
+		fill.width = width;
+	}
+	let label = format!("{:.0} HP", ***health);
+	if text.0 != label {
+		text.0 = label;
+	}
+}
+
+/// Lists the rate of every fever source, e.g. `+1%` for the one the player starts with
+fn update_fever_status(
+	player: Option<Single<(&FeverSources, Has<Feverish>), With<Player>>>,
+	sources: Query<&FeverSource>,
+	mut icon: Single<&mut BackgroundColor, With<FeverIcon>>,
+	mut text: Single<&mut Text, With<FeverStatusText>>,
+) {
+	let Some(player) = player else {
+		return;
+	};
+	let (fever_sources, feverish) = player.into_inner();
+
+	icon.set_if_neq(BackgroundColor(if feverish {
+		MAX_TEMPERATURE_COLOR
+	} else {
+		TRACK_COLOR
+	}));
+	let rates: Vec<String> = sources
+		.iter_many(fever_sources.iter())
+		.map(|source| format!("{:+.0}%", (**source - 1.0) * 100.0))
+		.collect();
+	let label = match (feverish, rates.is_empty()) {
+		(_, true) => "No fever sources".to_string(),
+		(true, false) => format!("Feverish: {}", rates.join(", ")),
+		(false, false) => format!("Fever sources: {}", rates.join(", ")),
+	};
+	if text.0 != label {
+		text.0 = label;
+	}
+}
+
+fn flash_on_fever_damage(
+	tick: On<FeverTick>,
+	player: Query<(&Temperature, &TemperatureThreshold), With<Player>>,
+	mut flashes: Query<&mut DamageFlash>,
+) {
+	let Ok((temperature, threshold)) = player.get(tick.entity) else {
+		return;
+	};
+	// Fever only deals damage above the threshold
+	if **temperature <= **threshold {
+		return;
+	}
+	for mut flash in &mut flashes {
+		flash.0 = 1.0;
+	}
+}
+
+fn fade_damage_flash(
+	mut flashes: Query<(&mut DamageFlash, &mut BackgroundColor)>,
+	time: Res<Time>,
+) {
+	for (mut flash, mut color) in &mut flashes {
+		if flash.0 <= 0.0 && color.0 == TRACK_COLOR {
+			continue;
+		}
+		flash.0 = (flash.0 - time.delta_secs() / DAMAGE_FLASH_SECS).max(0.0);
+		color.0 = TRACK_COLOR.mix(&DAMAGE_FLASH_COLOR, flash.0);
+	}
+}
@@ -1,7 +1,8 @@
 use bevy::app::App;
 
+mod hud;
 pub mod postprocess;
 
 pub fn plugin(app: &mut App) {
-	postprocess::plugin(app)
+	app.add_plugins((hud::plugin, postprocess::plugin));
 }
@@ -216,7 +216,9 @@ pub struct FeverPostProcessSettings {
 	pub fever: f32,
 	pub damage_threshold: f32,
 	pub damage_indicator: f32,
-	_pad: Vec2,
+	/// How strongly the player hallucinates from something they ate, from `0` to `1`
+	pub hallucination: f32,
+	_pad: f32,
 }
 
 impl Default for FeverPostProcessSettings {
@@ -227,7 +229,8 @@ impl Default for FeverPostProcessSettings {
 			fever: 0.,
 			damage_threshold: 0.,
 			damage_indicator: 0.,
-			_pad: Vec2::new(0., 0.),
+			hallucination: 0.,
+			_pad: 0.,
 		}
 	}
 }
@@ -1,45 +1,169 @@
 use bevy::prelude::*;
+use bevy_ahoy::prelude::PullObject;
+use bevy_enhanced_input::prelude::*;
 
 use crate::{
-	gameplay::interaction::AvailableInteraction, props::interactables::InteractableEntity,
-	screens::Screen, ui_layout::RootWidget,
+	gameplay::{
+		interaction::{AvailableInteraction, InteractionVerb},
+		player::input::{EatObject, Interact},
+		stomach::eat::EatRefused,
+	},
+	props::interactables::InteractableEntity,
+	screens::Screen,
+	theme::{input_glyph::ActionGlyphs, widget},
+	ui_layout::RootWidget,
 };
 
 pub(super) fn plugin(app: &mut App) {
-	app.add_systems(OnEnter(Screen::Gameplay), spawn_interaction_text)
-		.add_systems(Update, update_interaction_text);
+	app.add_systems(
+		OnEnter(Screen::Gameplay),
+		(spawn_interaction_text, spawn_eat_refused_message),
+	)
+	.add_systems(Update, (update_interaction_text, hide_eat_refused_message))
+	.add_observer(show_eat_refused_message);
 }
 
-/// Marker component for the [`Text`] node which displays the ability to interact
+/// Marker component for the node which displays what can be done with the targeted interactable
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
-struct InteractionHintText;
+struct InteractionHint;
+
+/// Marker component for the [`Text`] in a keycap showing what to press for a verb
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct InteractionGlyphText;
 
 fn spawn_interaction_text(mut commands: Commands) {
 	commands.spawn((
-		Text::new(""),
-		TextFont::from_font_size(37.0),
+		Name::new("Interaction Hint"),
+		InteractionHint,
+		Node {
+			display: Display::None,
+			align_items: AlignItems::Center,
+			column_gap: Val::Px(30.0),
+			..default()
+		},
 		DespawnOnExit(Screen::Gameplay),
-		InteractionHintText,
 		RootWidget,
 	));
 }
 
+/// The [`Text`] telling the player why something couldn't be eaten, shown for a moment after trying
+#[derive(Component, Debug, Default)]
+struct EatRefusedMessage {
+	hide_timer: Option<Timer>,
+}
+
+fn spawn_eat_refused_message(mut commands: Commands) {
+	commands.spawn((
+		Name::new("Eat Refused Message"),
+		EatRefusedMessage::default(),
+		Text::default(),
+		TextFont::from_font_size(30.0),
+		Node {
+			display: Display::None,
+			..default()
+		},
+		DespawnOnExit(Screen::Gameplay),
+		RootWidget,
+	));
+}
+
+fn show_eat_refused_message(
+	refused: On<EatRefused>,
+	message: Single<(&mut Text, &mut Node, &mut EatRefusedMessage)>,
+) {
+	let (mut text, mut node, mut message) = message.into_inner();
+	text.0 = refused.reason.message().to_string();
+	node.display = Display::Flex;
+	message.hide_timer = Some(Timer::from_seconds(2.0, TimerMode::Once));
+}
+
+fn hide_eat_refused_message(message: Single<(&mut Node, &mut EatRefusedMessage)>, time: Res<Time>) {
+	let (mut node, mut message) = message.into_inner();
+	let Some(timer) = &mut message.hide_timer else {
+		return;
+	};
+	if timer.tick(time.delta()).is_finished() {
+		message.hide_timer = None;
+		node.display = Display::None;
+	}
+}
+
+/// Shows a prompt per verb of the targeted interactable, each with the binding of its own action
 fn update_interaction_text(
-	mut interaction_text: Single<&mut Text, With<InteractionHintText>>,
+	hint: Single<(Entity, &mut Node), With<InteractionHint>>,
 	interaction_data: Res<AvailableInteraction>,
 	interactable: Query<&InteractableEntity>,
+	interact_action: Query<Entity, With<Action<Interact>>>,
+	eat_action: Query<Entity, With<Action<EatObject>>>,
+	pull_action: Query<Entity, With<Action<PullObject>>>,
+	glyphs: ActionGlyphs,
+	// The hint node and the prompts it shows, so they are only respawned when they change
+	mut shown_prompts: Local<(Option<Entity>, Vec<(String, String)>)>,
+	mut commands: Commands,
 ) {
-	***interaction_text = interaction_data
+	let (hint, mut hint_node) = hint.into_inner();
+	let interactable = interaction_data
 		.target_entity
-		.map_or("".to_string(), |entity| {
-			interactable
-				.get(entity)
-				.ok()
-				.map_or("Click: ???".to_string(), |interactable| {
-					interactable
-						.get_hover_text()
-						.map_or("".to_string(), |value| format!("Click:{value}"))
-				})
-		});
+		.and_then(|entity| interactable.get(entity).ok());
+	let prompts: Vec<(String, String)> = interactable
+		.into_iter()
+		.flat_map(|interactable| {
+			interaction_data.verbs.iter().filter_map(move |verb| {
+				let (glyph, text) = match verb {
+					InteractionVerb::Use => (
+						interact_action
+							.iter()
+							.find_map(|action| glyphs.glyph(action)),
+						interactable.get_use_text(),
+					),
+					InteractionVerb::Talk => (
+						interact_action
+							.iter()
+							.find_map(|action| glyphs.glyph(action)),
+						"Talk",
+					),
+					InteractionVerb::Eat => (
+						eat_action.iter().find_map(|action| glyphs.glyph(action)),
+						interactable.get_eat_text(),
+					),
+					InteractionVerb::PickUp => (
+						pull_action.iter().find_map(|action| glyphs.glyph(action)),
+						"Pick up",
+					),
+				};
+				Some((glyph?, text.to_string()))
+			})
+		})
+		.collect();
+	if shown_prompts.0 == Some(hint) && shown_prompts.1 == prompts {
+		return;
+	}
+
+	let display = if prompts.is_empty() {
+		Display::None
+	} else {
+		Display::Flex
+	};
+	if hint_node.display != display {
+		hint_node.display = display;
+	}
+	commands.entity(hint).despawn_related::<Children>();
+	for (glyph, text) in &prompts {
+		commands.spawn((
+			Name::new("Interaction Prompt"),
+			Node {
+				align_items: AlignItems::Center,
+				column_gap: Val::Px(10.0),
+				..default()
+			},
+			ChildOf(hint),
+			children![
+				widget::keycap(glyph.clone(), InteractionGlyphText),
+				(Text::new(text.clone()), TextFont::from_font_size(37.0)),
+			],
+		));
+	}
+	*shown_prompts = (Some(hint), prompts);
 }
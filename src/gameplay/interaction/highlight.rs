@@ -0,0 +1,138 @@
+//! Highlights entities by drawing their meshes a second time, with a glow added on top.
+//!
+//! The meshes keep their own materials, so animated ones like the CRT screens keep animating while highlighted.
+
+use std::any::TypeId;
+
+use bevy::{
+	camera::visibility::RenderLayers,
+	light::{NotShadowCaster, NotShadowReceiver},
+	mesh::skinning::SkinnedMesh,
+	prelude::*,
+};
+
+use crate::gameplay::{
+	interaction::{AvailableInteraction, InteractionSystems},
+	player::controls::ControlSettings,
+};
+
+pub(super) fn plugin(app: &mut App) {
+	app.add_plugins(highlight_plugin::<InteractionHighlight>);
+	app.add_systems(
+		Update,
+		update_highlight
+			.in_set(InteractionSystems::Highlight)
+			.after(InteractionSystems::Target),
+	);
+}
+
+/// A kind of highlight. Inserting it on an entity makes the meshes of the entity and its descendants glow,
+/// removing it stops the glow. Different kinds can be on the same entity at once.
+pub(crate) trait Highlight: Component {
+	/// Added on top of whatever the meshes show
+	const COLOR: LinearRgba;
+}
+
+/// Draws the highlight `H` on every entity it is inserted on
+pub(crate) fn highlight_plugin<H: Highlight>(app: &mut App) {
+	app.add_observer(add_highlight_overlays::<H>)
+		.add_observer(remove_highlight_overlays::<H>);
+}
+
+/// The interactable the player is targeting
+#[derive(Component, Debug)]
+pub(crate) struct InteractionHighlight;
+
+impl Highlight for InteractionHighlight {
+	// Bright enough to stand out in the dim levels without washing out the texture
+	const COLOR: LinearRgba = LinearRgba::new(0.25, 0.18, 0.08, 1.0);
+}
+
+/// A copy of a highlighted mesh that draws the glow of the [`Highlight`] with this [`TypeId`]
+#[derive(Component, Debug)]
+struct HighlightOverlay(TypeId);
+
+fn add_highlight_overlays<H: Highlight>(
+	add: On<Add, H>,
+	children: Query<&Children>,
+	meshes: Query<
+		(Entity, &Mesh3d, Option<&SkinnedMesh>, Option<&RenderLayers>),
+		Without<HighlightOverlay>,
+	>,
+	mut overlay_material: Local<Option<Handle<StandardMaterial>>>,
+	mut materials: ResMut<Assets<StandardMaterial>>,
+	mut commands: Commands,
+) {
+	let overlay_material = overlay_material
+		.get_or_insert_with(|| {
+			materials.add(StandardMaterial {
+				base_color: H::COLOR.into(),
+				alpha_mode: AlphaMode::Add,
+				unlit: true,
+				// Drawn exactly where the mesh it copies is
+				depth_bias: 1.0,
+				..default()
+			})
+		})
+		.clone();
+
+	for (mesh_entity, mesh, skin, render_layers) in
+		meshes.iter_many(std::iter::once(add.entity).chain(children.iter_descendants(add.entity)))
+	{
+		let mut overlay = commands.spawn((
+			Name::new("Highlight Overlay"),
+			HighlightOverlay(TypeId::of::<H>()),
+			mesh.clone(),
+			MeshMaterial3d(overlay_material.clone()),
+			NotShadowCaster,
+			NotShadowReceiver,
+			Pickable::IGNORE,
+			ChildOf(mesh_entity),
+		));
+		if let Some(skin) = skin {
+			overlay.insert(skin.clone());
+		}
+		if let Some(render_layers) = render_layers {
+			overlay.insert(render_layers.clone());
+		}
+	}
+}
+
+fn remove_highlight_overlays<H: Highlight>(
+	remove: On<Remove, H>,
+	children: Query<&Children>,
+	overlays: Query<&HighlightOverlay>,
+	mut commands: Commands,
+) {
+	for entity in children.iter_descendants(remove.entity) {
+		if overlays
+			.get(entity)
+			.is_ok_and(|overlay| overlay.0 == TypeId::of::<H>())
+		{
+			commands.entity(entity).try_despawn();
+		}
+	}
+}
+
+fn update_highlight(
+	available: Res<AvailableInteraction>,
+	settings: Res<ControlSettings>,
+	mut highlighted: Local<Option<Entity>>,
+	mut commands: Commands,
+) {
+	let target = available
+		.target_entity
+		.filter(|_| settings.highlight_interactables);
+	if *highlighted == target {
+		return;
+	}
+	if let Some(previous) = *highlighted {
+		commands
+			.entity(previous)
+			.try_remove::<InteractionHighlight>();
+	}
+	if let Some(target) = target {
+		commands.entity(target).try_insert(InteractionHighlight);
+	}
+	*highlighted = target;
+}
@@ -0,0 +1,345 @@
+use crate::gameplay::TargetnameEntityIndex;
+use crate::gameplay::player::camera::PlayerCameraParent;
+use crate::gameplay::player::input::{EatObject, Interact};
+use crate::props::interactables::InteractableEntity;
+use crate::props::logic_entity::YarnNode;
+use crate::third_party::avian3d::CollisionLayer;
+use avian3d::prelude::*;
+use bevy::platform::collections::HashSet;
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+
+pub(crate) mod highlight;
+
+/// [`Resource`] describing whether there is an interactable action available and optionally if there is a name for it.
+#[derive(Resource, Default)]
+pub struct AvailableInteraction {
+	pub target_entity: Option<Entity>,
+	/// What can be done with the target, in the order the prompts are shown
+	pub verbs: Vec<InteractionVerb>,
+}
+
+/// Something the player can do with an interactable. Each verb has its own button.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+pub enum InteractionVerb {
+	/// Triggers [`InteractEvent`] on the target, bound to [`Interact`]
+	Use,
+	/// Starts the target's dialogue through [`InteractEvent`], bound to [`Interact`]
+	Talk,
+	/// Triggers [`EatEvent`] on the target, bound to [`EatObject`]
+	Eat,
+	/// Picks the target up, which the character controller handles on its own
+	PickUp,
+}
+
+impl InteractionVerb {
+	/// The verbs available for an interactable
+	fn of(interactable: &InteractableEntity, is_dialogue: bool, is_dynamic: bool) -> Vec<Self> {
+		// Edible entities can only be talked to when they're usable, same as with using them
+		let interact = if is_dialogue && (!interactable.is_edible || interactable.is_usable) {
+			Some(Self::Talk)
+		} else if interactable.can_use() {
+			Some(Self::Use)
+		} else {
+			None
+		};
+		let eat = interactable.is_edible.then_some(Self::Eat);
+		let pick_up = is_dynamic.then_some(Self::PickUp);
+		interact.into_iter().chain(eat).chain(pick_up).collect()
+	}
+}
+
+/// [`Resource`] present while the player holds the button of a verb on an entity that needs [`InteractableEntity::hold_duration`].
+#[derive(Resource, Debug)]
+pub(crate) struct HoldingInteraction {
+	pub(crate) target_entity: Entity,
+	pub(crate) verb: InteractionVerb,
+	pub(crate) timer: Timer,
+}
+
+/// [`Event`] triggered when the specified entity was interacted with.
+#[derive(Event)]
+pub struct InteractEvent(pub Entity);
+
+/// [`Event`] triggered when the player eats the specified entity.
+#[derive(Event)]
+pub struct EatEvent(pub Entity);
+
+pub(super) fn plugin(app: &mut App) {
+	app.init_resource::<AvailableInteraction>()
+		.add_observer(interact_by_input_action)
+		.add_observer(eat_by_input_action);
+	app.add_plugins(highlight::plugin);
+	app.add_systems(
+		Update,
+		(
+			iquick_plz_do_not_kill_me.in_set(InteractionSystems::Target),
+			progress_hold_interaction
+				.run_if(resource_exists::<HoldingInteraction>)
+				.after(InteractionSystems::Target),
+		),
+	);
+}
+
+/// Where the interaction target is picked, so that systems reacting to it can be ordered after it
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum InteractionSystems {
+	Target,
+	/// Highlights the target, see [`highlight`]
+	Highlight,
+}
+
+fn interact_by_input_action(
+	_trigger: On<Start<Interact>>,
+	resource: Res<AvailableInteraction>,
+	entity_map: Option<Res<TargetnameEntityIndex>>,
+	interaction_query: Query<&InteractableEntity>,
+	mut commands: Commands,
+) {
+	let Some(&verb) = resource
+		.verbs
+		.iter()
+		.find(|verb| matches!(verb, InteractionVerb::Use | InteractionVerb::Talk))
+	else {
+		return;
+	};
+	start_verb(
+		verb,
+		&resource,
+		&interaction_query,
+		entity_map.as_deref(),
+		&mut commands,
+	);
+}
+
+fn eat_by_input_action(
+	_trigger: On<Start<EatObject>>,
+	resource: Res<AvailableInteraction>,
+	entity_map: Option<Res<TargetnameEntityIndex>>,
+	interaction_query: Query<&InteractableEntity>,
+	mut commands: Commands,
+) {
+	if resource.verbs.contains(&InteractionVerb::Eat) {
+		start_verb(
+			InteractionVerb::Eat,
+			&resource,
+			&interaction_query,
+			entity_map.as_deref(),
+			&mut commands,
+		);
+	}
+}
+
+/// Performs `verb` on the target right away, or starts holding if the target needs it
+fn start_verb(
+	verb: InteractionVerb,
+	resource: &AvailableInteraction,
+	interaction_query: &Query<&InteractableEntity>,
+	entity_map: Option<&TargetnameEntityIndex>,
+	commands: &mut Commands,
+) {
+	let Some(entity) = resource.target_entity else {
+		return;
+	};
+	let interactable = interaction_query.get(entity).ok();
+	if let Some(interactable) = interactable
+		&& interactable.needs_hold()
+	{
+		commands.insert_resource(HoldingInteraction {
+			target_entity: entity,
+			verb,
+			timer: Timer::from_seconds(interactable.hold_duration, TimerMode::Once),
+		});
+		return;
+	}
+	perform_verb(verb, entity, interactable, entity_map, commands);
+}
+
+/// Triggers the event of `verb` on `entity`, and [`InteractEvent`] on the entities it relays to if `verb` is its main one
+fn perform_verb(
+	verb: InteractionVerb,
+	entity: Entity,
+	interactable: Option<&InteractableEntity>,
+	entity_map: Option<&TargetnameEntityIndex>,
+	commands: &mut Commands,
+) {
+	let relays_on_eat = interactable.is_some_and(InteractableEntity::relays_on_eat);
+	let relays = match verb {
+		InteractionVerb::Use | InteractionVerb::Talk => {
+			commands.trigger(InteractEvent(entity));
+			!relays_on_eat
+		}
+		InteractionVerb::Eat => {
+			commands.trigger(EatEvent(entity));
+			relays_on_eat
+		}
+		InteractionVerb::PickUp => false,
+	};
+	if !relays {
+		return;
+	}
+
+	// Also try shooting events to friends!
+	if let (Some(entity_map), Some(interactable)) = (entity_map, interactable) {
+		let relay_name = interactable.get_interaction_relay();
+		let objective_name = interactable.get_completes_subobjective();
+		let targets = relay_name
+			.into_iter()
+			.chain(objective_name)
+			.flat_map(|targetname| entity_map.get_entity_by_targetname(targetname).iter())
+			.copied()
+			.collect::<HashSet<Entity>>();
+		for &related in targets.iter() {
+			// Don't double-trigger
+			if related != entity {
+				commands.trigger(InteractEvent(related));
+			}
+		}
+	}
+}
+
+/// Performs the held verb once its button was held long enough.
+/// Releasing the button, looking away or walking out of range cancels it.
+fn progress_hold_interaction(
+	mut holding: ResMut<HoldingInteraction>,
+	available: Res<AvailableInteraction>,
+	interact_action: Query<&ActionState, With<Action<Interact>>>,
+	eat_action: Query<&ActionState, With<Action<EatObject>>>,
+	entity_map: Option<Res<TargetnameEntityIndex>>,
+	interaction_query: Query<&InteractableEntity>,
+	time: Res<Time>,
+	mut commands: Commands,
+) {
+	let interactable = interaction_query.get(holding.target_entity).ok();
+	let fired = |state: &ActionState| *state == ActionState::Fired;
+	let still_held = match holding.verb {
+		InteractionVerb::Eat => eat_action.iter().any(fired),
+		_ => interact_action.iter().any(fired),
+	};
+	if !still_held
+		|| available.target_entity != Some(holding.target_entity)
+		|| !available.verbs.contains(&holding.verb)
+	{
+		commands.remove_resource::<HoldingInteraction>();
+		if let (Some(entity_map), Some(relay)) = (
+			entity_map,
+			interactable.and_then(InteractableEntity::get_on_hold_cancelled),
+		) {
+			for &related in entity_map.get_entity_by_targetname(relay) {
+				commands.trigger(InteractEvent(related));
+			}
+		}
+		return;
+	}
+
+	if holding.timer.tick(time.delta()).just_finished() {
+		commands.remove_resource::<HoldingInteraction>();
+		perform_verb(
+			holding.verb,
+			holding.target_entity,
+			interactable,
+			entity_map.as_deref(),
+			&mut commands,
+		);
+	}
+}
+
+/// Picks what the player interacts with.
+/// Whatever is right under the crosshair wins, otherwise the interactable closest to the center of view within a cone,
+/// which makes small props like pens easy to target.
+fn iquick_plz_do_not_kill_me(
+	spatial: SpatialQuery,
+	cam: Single<&Transform, With<PlayerCameraParent>>,
+	pickable: Query<(Entity, &Pickable)>,
+	sensors: Query<Entity, With<Sensor>>,
+	interaction_query: Query<&InteractableEntity>,
+	yarn_nodes: Query<&YarnNode>,
+	rigid_bodies: Query<&RigidBody>,
+	mut resource: ResMut<AvailableInteraction>,
+	collider: Query<(&ColliderOf, &ColliderAabb)>,
+) {
+	let transform = cam.into_inner();
+	let ignored = pickable
+		.iter()
+		.filter_map(|(entity, pickable)| {
+			if *pickable == Pickable::IGNORE {
+				Some(entity)
+			} else {
+				None
+			}
+		})
+		.chain(sensors.iter());
+	let filter = SpatialQueryFilter::from_excluded_entities(ignored).with_mask([
+		CollisionLayer::Default,
+		CollisionLayer::Prop,
+		CollisionLayer::Character,
+		CollisionLayer::Dialog,
+	]);
+	let is_interactable = |body: Entity| {
+		interaction_query
+			.get(body)
+			.is_ok_and(InteractableEntity::is_active)
+	};
+	// The body whose collider is hit first when looking from the camera towards `direction`
+	let first_hit = |direction: Dir3, max_distance: f32| {
+		spatial
+			.cast_ray(
+				transform.translation,
+				direction,
+				max_distance,
+				true,
+				&filter,
+			)
+			.and_then(|hit| collider.get(hit.entity).ok())
+			.map(|(collider_of, _)| collider_of.body)
+	};
+
+	const INTERACTION_DISTANCE: f32 = 1.8;
+	/// Half the opening angle of the cone in which props are targeted without being right under the crosshair
+	const TARGET_CONE_ANGLE: f32 = 12.0_f32.to_radians();
+
+	let in_cone = || {
+		let candidates = spatial.shape_intersections(
+			&Collider::sphere(INTERACTION_DISTANCE),
+			transform.translation,
+			Quat::IDENTITY,
+			&filter,
+		);
+		collider
+			.iter_many(candidates)
+			.filter(|(collider_of, _)| is_interactable(collider_of.body))
+			.filter_map(|(collider_of, aabb)| {
+				let to_center = aabb.center() - transform.translation;
+				let distance = to_center.length();
+				let direction = Dir3::new(to_center).ok()?;
+				let angle = direction.angle_between(*transform.forward());
+				if angle > TARGET_CONE_ANGLE || distance > INTERACTION_DISTANCE {
+					return None;
+				}
+				// Don't target things through walls
+				if first_hit(direction, distance).is_some_and(|hit| hit != collider_of.body) {
+					return None;
+				}
+				// Being in the center of view matters more than being close
+				let score = angle / TARGET_CONE_ANGLE + 0.5 * distance / INTERACTION_DISTANCE;
+				Some((collider_of.body, score))
+			})
+			.min_by(|(_, a), (_, b)| a.total_cmp(b))
+			.map(|(body, _)| body)
+	};
+	let target = first_hit(transform.forward(), INTERACTION_DISTANCE)
+		.filter(|&body| is_interactable(body))
+		.or_else(in_cone);
+
+	resource.target_entity = target;
+	resource.verbs = target
+		.and_then(|entity| {
+			let interactable = interaction_query.get(entity).ok()?;
+			let is_dialogue = yarn_nodes
+				.get(entity)
+				.is_ok_and(|node| !node.is_non_dialogue);
+			let is_dynamic = rigid_bodies.get(entity).is_ok_and(RigidBody::is_dynamic);
+			Some(InteractionVerb::of(interactable, is_dialogue, is_dynamic))
+		})
+		.unwrap_or_default();
+}
@@ -51,6 +51,7 @@ pub(super) fn plugin(app: &mut App) {
 	);
 
 	app.add_observer(advance_level);
+	app.add_observer(restart_level);
 	app.init_resource::<CurrentLevel>();
 }
 
@@ -119,6 +120,8 @@ pub(crate) fn spawn_level(
 				ObjectiveEntity {
 					target: None,
 					objective_order: -1.0,
+					objective_location: Some("IntroCRT".to_string()),
+					..default()
 				},
 			));
 
@@ -160,6 +163,8 @@ pub(crate) fn spawn_level(
 				ObjectiveEntity {
 					target: None,
 					objective_order: -1.0,
+					objective_location: Some("Day2CRT".to_string()),
+					..default()
 				},
 			));
 			let level_two_assets = level_two_assets.expect("If we don't have level two assets when spawning level two, we're in deep shit. Sorry player, we bail here.");
@@ -201,6 +206,7 @@ pub(crate) fn spawn_level(
 				ObjectiveEntity {
 					target: None,
 					objective_order: -1.,
+					..default()
 				},
 			));
 			let level_three_assets = level_three_assets.expect("If we don't have level three assets when spawning level three, we're in deep shit. Sorry player, we bail here.");
@@ -421,6 +427,19 @@ fn advance_level(
 	};
 }
 
+/// Spawns the current level again from its already loaded assets
+#[derive(Event)]
+pub(crate) struct RestartLevel;
+
+fn restart_level(
+	_restart: On<RestartLevel>,
+	mut ns_loading_screen: ResMut<NextState<LoadingScreen>>,
+	mut ns_screen: ResMut<NextState<Screen>>,
+) {
+	ns_loading_screen.set(LoadingScreen::Assets);
+	ns_screen.set(Screen::Loading);
+}
+
 fn advance_level_command<T: Asset + Resource + Clone + FromWorld>() -> impl Command {
 	|world: &mut World| {
 		let value = T::from_world(world);
@@ -26,6 +26,7 @@ pub(crate) mod objectives;
 pub(crate) mod player;
 pub(crate) mod scripting;
 pub(crate) mod stomach;
+pub(crate) mod yarn_variables;
 
 pub(crate) mod fever;
 
@@ -39,6 +40,7 @@ pub(super) fn plugin(app: &mut App) {
 		objectives::plugin,
 		player::plugin,
 		stomach::plugin,
+		yarn_variables::plugin,
 		// This plugin preloads the level,
 		// so make sure to add it last.
 		level::plugin,
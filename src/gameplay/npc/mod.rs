@@ -10,7 +10,10 @@ use bevy_trenchbroom::prelude::*;
 use crate::{
 	animation::AnimationState,
 	asset_tracking::LoadResource,
-	gameplay::TargetName,
+	gameplay::{
+		TargetName,
+		core::{BaseTemperature, Temperature, TemperatureSensor},
+	},
 	props::{interactables::InteractableEntity, logic_entity::YarnNode},
 	third_party::{
 		avian3d::CollisionLayer,
@@ -66,6 +69,8 @@ fn on_add(add: On<Add, Npc>, mut commands: Commands, assets: Res<AssetServer>) {
 			},
 			ColliderDensity(1_000.0),
 			RigidBody::Kinematic,
+			Temperature::default(),
+			BaseTemperature::default(),
 			AnimationState::<NpcAnimationState>::default(),
 			AnimationPlayerAncestor,
 			CollisionLayers::new(
@@ -74,6 +79,14 @@ fn on_add(add: On<Add, Npc>, mut commands: Commands, assets: Res<AssetServer>) {
 			),
 			//enemy_htn(),
 		))
+		.with_child((
+			Name::new("Npc Temperature Sensor"),
+			TemperatureSensor,
+			CollisionLayers::new(
+				CollisionLayer::Sensor,
+				[CollisionLayer::Default, CollisionLayer::Prop],
+			),
+		))
 		.with_child((
 			Name::new("Npc Model"),
 			SceneRoot(assets.load_trenchbroom_model::<Npc>()),
@@ -0,0 +1,92 @@
+//! Conditions that complete an objective on their own, without anything having to interact with it.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_trenchbroom::prelude::*;
+
+use crate::gameplay::{
+	TargetName, TargetnameEntityIndex,
+	core::Temperature,
+	objectives::{Objective, ObjectiveCompleted, PendingObjective},
+	player::Player,
+	stomach::Stomach,
+};
+
+pub(super) fn plugin(app: &mut App) {
+	app.add_systems(FixedUpdate, evaluate_objective_conditions);
+}
+
+/// Completes the objective as soon as a gameplay condition holds.
+/// Evaluated every fixed tick while the objective is pending.
+#[derive(Default)]
+#[base_class]
+pub struct ObjectiveCondition {
+	/// What has to hold for the objective to complete
+	pub condition: ObjectiveConditionKind,
+	/// The targetname (prefix) the condition is about, see [`ObjectiveConditionKind`]
+	pub condition_target: String,
+	/// The number the condition compares against, see [`ObjectiveConditionKind`]
+	pub condition_amount: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect, FgdType)]
+pub enum ObjectiveConditionKind {
+	/// Never completes on its own
+	#[default]
+	None,
+	/// The stomach contains at least `condition_amount` props whose targetname starts with `condition_target`
+	StomachContains,
+	/// The player's temperature is above `condition_amount`
+	TemperatureAbove,
+	/// The player is inside the sensor named `condition_target`
+	PlayerInside,
+	/// Every entity named `condition_target` has been despawned
+	Despawned,
+}
+
+/// Marks objectives whose [`ObjectiveConditionKind::Despawned`] target has existed,
+/// so that they don't complete before the level has finished spawning it.
+#[derive(Component, Debug)]
+struct ConditionTargetSpawned;
+
+fn evaluate_objective_conditions(
+	objectives: Query<
+		(Entity, &ObjectiveCondition, Has<ConditionTargetSpawned>),
+		(With<Objective>, PendingObjective),
+	>,
+	stomachs: Query<&Stomach>,
+	target_names: Query<&TargetName>,
+	player: Single<(Entity, &Temperature), With<Player>>,
+	entity_index: Res<TargetnameEntityIndex>,
+	collisions: Collisions,
+	mut commands: Commands,
+) {
+	let (player, temperature) = *player;
+	for (entity, condition, target_spawned) in &objectives {
+		let targets = entity_index.get_entity_by_targetname(&condition.condition_target);
+		let holds = match condition.condition {
+			ObjectiveConditionKind::None => false,
+			ObjectiveConditionKind::StomachContains => {
+				let count = stomachs
+					.iter()
+					.flat_map(|stomach| target_names.iter_many(&stomach.contents))
+					.filter(|name| name.starts_with(&condition.condition_target))
+					.count();
+				count as f32 >= condition.condition_amount.max(1.0)
+			}
+			ObjectiveConditionKind::TemperatureAbove => **temperature > condition.condition_amount,
+			ObjectiveConditionKind::PlayerInside => targets
+				.iter()
+				.any(|&sensor| collisions.contains(sensor, player)),
+			ObjectiveConditionKind::Despawned => {
+				if !targets.is_empty() && !target_spawned {
+					commands.entity(entity).insert(ConditionTargetSpawned);
+				}
+				target_spawned && targets.is_empty()
+			}
+		};
+		if holds {
+			commands.entity(entity).insert(ObjectiveCompleted);
+		}
+	}
+}
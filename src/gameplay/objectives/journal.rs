@@ -0,0 +1,89 @@
+//! A record of the objectives completed during a playthrough, shown in the journal menu.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+	gameplay::{
+		TargetnameEntityIndex,
+		level::CurrentLevel,
+		objectives::{Objective, ObjectiveCompleted, SubObjectiveOf},
+	},
+	props::logic_entity::ObjectiveEntity,
+	screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+	app.init_resource::<ObjectiveJournal>();
+	app.add_observer(record_completed_objective);
+	app.add_systems(Update, tick_playtime.run_if(in_state(Screen::Gameplay)));
+	app.add_systems(OnEnter(Screen::Title), reset_journal);
+}
+
+/// Every objective completed during this playthrough, in the order they were completed.
+/// Unlike the objectives themselves, this survives level changes.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+pub(crate) struct ObjectiveJournal {
+	pub(crate) entries: Vec<JournalEntry>,
+	/// How long the player has spent in gameplay, not counting pauses
+	pub(crate) playtime: Duration,
+}
+
+#[derive(Reflect, Debug, Clone)]
+pub(crate) struct JournalEntry {
+	pub(crate) description: String,
+	pub(crate) flavor: Option<String>,
+	pub(crate) level: CurrentLevel,
+	/// The playtime at which the objective was completed
+	pub(crate) completed_at: Duration,
+	/// 0 for top-level objectives, 1 for their sub-objectives and so on
+	pub(crate) depth: usize,
+}
+
+fn record_completed_objective(
+	add: On<Add, ObjectiveCompleted>,
+	objectives: Query<(&Objective, &ObjectiveEntity)>,
+	parents: Query<&SubObjectiveOf>,
+	level: Res<CurrentLevel>,
+	mut journal: ResMut<ObjectiveJournal>,
+) {
+	let Ok((objective, objective_entity)) = objectives.get(add.entity) else {
+		return;
+	};
+	let completed_at = journal.playtime;
+	journal.entries.push(JournalEntry {
+		description: objective.description.clone(),
+		flavor: objective_entity.objective_flavor.clone(),
+		level: *level,
+		completed_at,
+		depth: parents.iter_ancestors(add.entity).count(),
+	});
+}
+
+fn tick_playtime(time: Res<Time>, mut journal: ResMut<ObjectiveJournal>) {
+	journal.playtime += time.delta();
+}
+
+/// Starts every playthrough with an empty journal
+fn reset_journal(mut journal: ResMut<ObjectiveJournal>) {
+	*journal = default();
+}
+
+pub(crate) fn set_dialogue_objective_flavor(
+	In((identifier, flavor)): In<(String, String)>,
+	mut objective_query: Query<&mut ObjectiveEntity>,
+	entity_name_index: Res<TargetnameEntityIndex>,
+) {
+	let mut found = false;
+	for &entity in entity_name_index.get_entity_by_targetname(&identifier) {
+		if let Ok(mut objective) = objective_query.get_mut(entity) {
+			objective.objective_flavor = Some(flavor.clone());
+			found = true;
+		}
+	}
+	if !found {
+		warn!("Failed to set journal flavor of objective {identifier}: no such objective found");
+	}
+}
@@ -0,0 +1,206 @@
+//! A HUD marker that points at the location of the current objective.
+//!
+//! The location is the `objective_location` targetname of the first pending (sub-)objective that has one.
+//! While the location is on screen the marker hovers over it, otherwise an arrow at the edge of the screen points towards it.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{
+	PostPhysicsAppSystems,
+	font::VARIABLE_FONT,
+	gameplay::{
+		TargetnameEntityIndex,
+		objectives::{CurrentObjective, PendingObjective, SubObjectives},
+		player::camera::WorldModelCamera,
+	},
+	props::logic_entity::ObjectiveEntity,
+	screens::Screen,
+	third_party::bevy_yarnspinner::ConversationRunner,
+};
+use bevy_yarnspinner::prelude::DialogueRunner;
+
+pub(super) fn plugin(app: &mut App) {
+	app.add_systems(OnEnter(Screen::Gameplay), spawn_objective_marker);
+	app.add_systems(
+		Update,
+		(
+			update_objective_marker.after(super::update_current_objective),
+			fade_objective_marker,
+		)
+			.chain()
+			.in_set(PostPhysicsAppSystems::ChangeUi),
+	);
+}
+
+/// The root node of the marker, positioned over the objective's location
+#[derive(Component, Debug, Default)]
+struct ObjectiveMarker {
+	/// How visible the marker should be, [`fade_objective_marker`] eases towards it
+	target_alpha: f32,
+	alpha: f32,
+}
+
+#[derive(Component, Debug)]
+struct ObjectiveMarkerArrow;
+
+#[derive(Component, Debug)]
+struct ObjectiveMarkerDistance;
+
+fn spawn_objective_marker(mut commands: Commands) {
+	commands.spawn((
+		Name::new("Objective Marker"),
+		ObjectiveMarker::default(),
+		Node {
+			position_type: PositionType::Absolute,
+			width: Px(MARKER_SIZE.x),
+			height: Px(MARKER_SIZE.y),
+			flex_direction: FlexDirection::Column,
+			align_items: AlignItems::Center,
+			justify_content: JustifyContent::Center,
+			..default()
+		},
+		Visibility::Hidden,
+		Pickable::IGNORE,
+		DespawnOnExit(Screen::Gameplay),
+		children![
+			(
+				ObjectiveMarkerDistance,
+				Text::default(),
+				TextFont {
+					font: VARIABLE_FONT,
+					font_size: 14.0,
+					weight: FontWeight(700),
+					..default()
+				},
+				TextColor(Color::NONE),
+				Pickable::IGNORE,
+			),
+			(
+				ObjectiveMarkerArrow,
+				Text::new("▼"),
+				TextFont {
+					font: VARIABLE_FONT,
+					font_size: 20.0,
+					..default()
+				},
+				TextColor(Color::NONE),
+				UiTransform::IDENTITY,
+				Pickable::IGNORE,
+			),
+		],
+	));
+}
+
+fn update_objective_marker(
+	current_objective: Res<CurrentObjective>,
+	objectives: Query<&ObjectiveEntity, PendingObjective>,
+	sub_objectives: Query<&SubObjectives>,
+	entity_index: Res<TargetnameEntityIndex>,
+	transforms: Query<&GlobalTransform>,
+	camera: Single<(&Camera, &GlobalTransform), With<WorldModelCamera>>,
+	conversation: Query<&DialogueRunner, With<ConversationRunner>>,
+	ui_scale: Res<UiScale>,
+	marker: Single<(&mut ObjectiveMarker, &mut Node, &mut Visibility)>,
+	mut arrow: Single<&mut UiTransform, With<ObjectiveMarkerArrow>>,
+	mut distance_text: Single<&mut Text, With<ObjectiveMarkerDistance>>,
+) {
+	let (mut marker, mut node, mut visibility) = marker.into_inner();
+	let (camera, camera_transform) = *camera;
+
+	let location = (**current_objective)
+		.and_then(|objective| objective_location(objective, &objectives, &sub_objectives))
+		.and_then(|location| {
+			entity_index
+				.get_entity_by_targetname(location)
+				.iter()
+				.find_map(|entity| transforms.get(*entity).ok())
+		})
+		.map(GlobalTransform::translation);
+	let (Some(location), Some(viewport_size)) = (location, camera.logical_viewport_size()) else {
+		*visibility = Visibility::Hidden;
+		marker.alpha = 0.0;
+		return;
+	};
+	*visibility = Visibility::Inherited;
+
+	let distance = camera_transform.translation().distance(location);
+	let in_dialogue = conversation.iter().any(DialogueRunner::is_running);
+	marker.target_alpha = if in_dialogue {
+		0.0
+	} else {
+		((distance - FADE_START_DISTANCE) / (FADE_END_DISTANCE - FADE_START_DISTANCE))
+			.clamp(0.0, 1.0)
+	};
+	distance_text.0 = format!("{distance:.0}m");
+
+	let screen_size = viewport_size / ui_scale.0;
+	let center = screen_size / 2.0;
+	let on_screen = camera
+		.world_to_viewport(camera_transform, location)
+		.ok()
+		.map(|position| position / ui_scale.0)
+		.filter(|position| {
+			position.cmpge(Vec2::splat(EDGE_MARGIN)).all()
+				&& position.cmple(screen_size - EDGE_MARGIN).all()
+		});
+	let position = if let Some(position) = on_screen {
+		arrow.rotation = Rot2::IDENTITY;
+		// Hover just above the location, with the arrow pointing down at it
+		position - Vec2::Y * MARKER_SIZE.y / 2.0
+	} else {
+		// The camera looks down its local -Z, and the UI's Y axis points down
+		let local = camera_transform
+			.affine()
+			.inverse()
+			.transform_point3(location);
+		let direction = Vec2::new(local.x, -local.y)
+			.try_normalize()
+			.unwrap_or(Vec2::Y);
+		arrow.rotation = Rot2::radians(Vec2::Y.angle_to(direction));
+		// Push the marker out from the center until it touches the margin
+		let half_extents = center - EDGE_MARGIN;
+		let scale = (half_extents / direction.abs().max(Vec2::splat(f32::EPSILON))).min_element();
+		center + direction * scale
+	};
+	node.left = Px(position.x - MARKER_SIZE.x / 2.0);
+	node.top = Px(position.y - MARKER_SIZE.y / 2.0);
+}
+
+/// The targetname the marker should point at, preferring the first unfinished sub-objective with a location
+fn objective_location<'a>(
+	objective: Entity,
+	objectives: &'a Query<&ObjectiveEntity, PendingObjective>,
+	sub_objectives: &Query<&SubObjectives>,
+) -> Option<&'a str> {
+	sub_objectives
+		.iter_descendants_depth_first(objective)
+		.chain(std::iter::once(objective))
+		.filter_map(|entity| objectives.get(entity).ok())
+		.find_map(|objective| objective.objective_location.as_deref())
+}
+
+fn fade_objective_marker(
+	time: Res<Time>,
+	mut marker: Single<(&mut ObjectiveMarker, &Children)>,
+	mut colors: Query<&mut TextColor>,
+) {
+	let (marker, children) = &mut *marker;
+	marker.alpha = marker.alpha.lerp(
+		marker.target_alpha,
+		1.0 - (-FADE_SPEED * time.delta_secs()).exp(),
+	);
+	let color = Color::WHITE.with_alpha(marker.alpha);
+	let mut colors = colors.iter_many_mut(children.iter());
+	while let Some(mut text_color) = colors.fetch_next() {
+		text_color.set_if_neq(TextColor(color));
+	}
+}
+
+const MARKER_SIZE: Vec2 = Vec2::new(60.0, 44.0);
+/// How far from the edges of the screen the marker stays
+const EDGE_MARGIN: f32 = 40.0;
+/// Closer than this, the marker is invisible
+const FADE_START_DISTANCE: f32 = 3.0;
+/// Further than this, the marker is fully visible
+const FADE_END_DISTANCE: f32 = 6.0;
+const FADE_SPEED: f32 = 8.0;
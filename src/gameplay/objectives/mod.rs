@@ -4,25 +4,52 @@ use bevy::{
 };
 use bevy_trenchbroom::prelude::*;
 
-use crate::gameplay::level::AdvanceLevel;
+use crate::gameplay::level::{AdvanceLevel, RestartLevel};
 use crate::{
 	gameplay::{TargetName, TargetnameEntityIndex, interaction::InteractEvent},
 	props::logic_entity::ObjectiveEntity,
 	screens::Screen,
 };
 
+pub(crate) mod condition;
+pub(crate) mod journal;
+mod marker;
 pub(crate) mod ui;
 
 pub(super) fn plugin(app: &mut App) {
-	app.add_plugins(ui::plugin);
+	app.add_plugins((
+		ui::plugin,
+		marker::plugin,
+		journal::plugin,
+		condition::plugin,
+	));
 	app.init_resource::<CurrentObjective>();
 
 	app.add_systems(
 		Update,
-		(update_current_objective, trigger_all_objectives_done).chain(),
+		(
+			tick_objective_countdowns,
+			update_current_objective,
+			trigger_all_objectives_done,
+		)
+			.chain(),
+	);
+	app.add_systems(
+		Update,
+		restart_failed_level.run_if(resource_exists::<LevelFailed>.and(in_state(Screen::Gameplay))),
+	);
+	app.add_systems(OnExit(Screen::Gameplay), |mut commands: Commands| {
+		commands.remove_resource::<LevelFailed>();
+	});
+	app.add_observer(watch_for_completions)
+		.add_observer(setup_objective_state)
+		.add_observer(relay_completed_objective)
+		.add_observer(relay_failed_objective)
+		.add_observer(fail_level);
+	app.add_systems(
+		PostUpdate,
+		(complete_parent_objectives, fail_parent_objectives),
 	);
-	app.add_observer(watch_for_completions);
-	app.add_systems(PostUpdate, complete_parent_objectives);
 }
 
 #[derive(Resource, Reflect, Debug, Deref, Default, PartialEq)]
@@ -61,6 +88,21 @@ impl Objective {
 #[derive(Component, Debug, Default)]
 pub struct ObjectiveCompleted;
 
+/// Marker component for failed objectives. A failed objective can no longer be completed.
+#[derive(Component, Debug, Default)]
+pub struct ObjectiveFailed;
+
+/// Marker component for objectives that don't have to be completed to advance the level.
+#[derive(Component, Debug, Default)]
+pub struct OptionalObjective;
+
+/// The time left to complete an objective. Only ticks while the objective is, or is part of, the [`CurrentObjective`].
+#[derive(Component, Debug, Deref, DerefMut)]
+pub struct ObjectiveCountdown(pub Timer);
+
+/// Objectives that are neither completed nor failed.
+pub(crate) type PendingObjective = (Without<ObjectiveCompleted>, Without<ObjectiveFailed>);
+
 /// A relationship component linking a sub-objective to its parent objective.
 #[derive(Component, Debug)]
 #[relationship(relationship_target = SubObjectives)]
@@ -77,7 +119,7 @@ pub struct SubObjectives(Vec<Entity>);
 
 fn watch_for_completions(
 	trigger: On<InteractEvent>,
-	objective_query: Query<(), With<Objective>>,
+	objective_query: Query<(), (With<Objective>, PendingObjective)>,
 	mut commands: Commands,
 ) {
 	if objective_query.contains(trigger.0) {
@@ -85,6 +127,61 @@ fn watch_for_completions(
 	}
 }
 
+/// Turns the TrenchBroom settings of an objective into the components that drive its state
+fn setup_objective_state(
+	insert: On<Insert, ObjectiveEntity>,
+	objectives: Query<&ObjectiveEntity>,
+	mut commands: Commands,
+) {
+	let Ok(objective) = objectives.get(insert.entity) else {
+		return;
+	};
+	let mut entity = commands.entity(insert.entity);
+	if objective.objective_optional {
+		entity.insert(OptionalObjective);
+	} else {
+		entity.remove::<OptionalObjective>();
+	}
+	if objective.objective_time_limit > 0.0 {
+		entity.insert(ObjectiveCountdown(Timer::from_seconds(
+			objective.objective_time_limit,
+			TimerMode::Once,
+		)));
+	} else {
+		entity.remove::<ObjectiveCountdown>();
+	}
+}
+
+fn relay_completed_objective(
+	add: On<Add, ObjectiveCompleted>,
+	objectives: Query<&ObjectiveEntity>,
+	entity_index: Res<TargetnameEntityIndex>,
+	mut commands: Commands,
+) {
+	if let Ok(objective) = objectives.get(add.entity)
+		&& let Some(target) = &objective.objective_on_complete
+	{
+		for &entity in entity_index.get_entity_by_targetname(target) {
+			commands.trigger(InteractEvent(entity));
+		}
+	}
+}
+
+fn relay_failed_objective(
+	add: On<Add, ObjectiveFailed>,
+	objectives: Query<&ObjectiveEntity>,
+	entity_index: Res<TargetnameEntityIndex>,
+	mut commands: Commands,
+) {
+	if let Ok(objective) = objectives.get(add.entity)
+		&& let Some(target) = &objective.objective_on_fail
+	{
+		for &entity in entity_index.get_entity_by_targetname(target) {
+			commands.trigger(InteractEvent(entity));
+		}
+	}
+}
+
 pub(crate) fn create_dialogue_objective(
 	In((identifier, description, order)): In<(String, String, f32)>,
 	mut commands: Commands,
@@ -95,6 +192,24 @@ pub(crate) fn create_dialogue_objective(
 		ObjectiveEntity {
 			target: None,
 			objective_order: order,
+			..default()
+		},
+		Objective::new(description),
+	));
+}
+
+pub(crate) fn create_dialogue_optional_objective(
+	In((identifier, description, order)): In<(String, String, f32)>,
+	mut commands: Commands,
+) {
+	commands.spawn((
+		Name::new(format!("Optional Objective: {identifier}")),
+		TargetName::new(identifier),
+		ObjectiveEntity {
+			target: None,
+			objective_order: order,
+			objective_optional: true,
+			..default()
 		},
 		Objective::new(description),
 	));
@@ -110,6 +225,7 @@ pub(crate) fn create_dialogue_subobjective(
 		ObjectiveEntity {
 			target: Some(parent_identifier),
 			objective_order: 0.0,
+			..default()
 		},
 		Objective::new(description),
 	));
@@ -118,7 +234,7 @@ pub(crate) fn create_dialogue_subobjective(
 pub(crate) fn complete_dialogue_objective(
 	In(identifier): In<String>,
 	mut commands: Commands,
-	objective_query: Query<(), With<Objective>>,
+	objective_query: Query<(), (With<Objective>, PendingObjective)>,
 	entity_name_index: Res<TargetnameEntityIndex>,
 ) {
 	for entity in entity_name_index
@@ -130,6 +246,21 @@ pub(crate) fn complete_dialogue_objective(
 	}
 }
 
+pub(crate) fn fail_dialogue_objective(
+	In(identifier): In<String>,
+	mut commands: Commands,
+	objective_query: Query<(), (With<Objective>, PendingObjective)>,
+	entity_name_index: Res<TargetnameEntityIndex>,
+) {
+	for entity in entity_name_index
+		.get_entity_by_targetname(&identifier)
+		.iter()
+		.filter(|entity| objective_query.contains(**entity))
+	{
+		commands.entity(*entity).insert(ObjectiveFailed);
+	}
+}
+
 pub(crate) fn get_dialogue_current_objective(
 	current_objective: Res<CurrentObjective>,
 	objective_query: Query<&TargetName>,
@@ -143,10 +274,24 @@ pub(crate) fn get_dialogue_current_objective(
 #[derive(Event)]
 pub(crate) struct AllObjectivesDone;
 
+/// Advances the level once every required objective is completed.
+/// A failed required objective restarts the level instead, see [`LevelFailed`].
 fn trigger_all_objectives_done(
+	failed_objectives: Query<
+		(),
+		(
+			With<ObjectiveFailed>,
+			Without<OptionalObjective>,
+			Without<SubObjectiveOf>,
+		),
+	>,
 	not_done_objectives: Query<
 		(Entity, &ObjectiveEntity),
-		(Without<ObjectiveCompleted>, Without<SubObjectiveOf>),
+		(
+			PendingObjective,
+			Without<OptionalObjective>,
+			Without<SubObjectiveOf>,
+		),
 	>,
 	done_objectives: Query<
 		(Entity, &ObjectiveEntity),
@@ -154,40 +299,103 @@ fn trigger_all_objectives_done(
 	>,
 	mut commands: Commands,
 ) {
-	if not_done_objectives.is_empty() && !done_objectives.is_empty() {
+	if failed_objectives.is_empty() && not_done_objectives.is_empty() && !done_objectives.is_empty()
+	{
 		commands.trigger(AllObjectivesDone);
 		commands.trigger(AdvanceLevel);
 	}
 }
 
+/// [`Resource`] present while the level is about to restart because a required objective failed.
+/// The failure stays on screen until then.
+#[derive(Resource, Debug, Deref, DerefMut)]
+struct LevelFailed(Timer);
+
+fn fail_level(
+	add: On<Add, ObjectiveFailed>,
+	required: Query<(), (Without<OptionalObjective>, Without<SubObjectiveOf>)>,
+	level_failed: Option<Res<LevelFailed>>,
+	mut commands: Commands,
+) {
+	if required.contains(add.entity) && level_failed.is_none() {
+		commands.insert_resource(LevelFailed(Timer::from_seconds(
+			ui::FAILED_OBJECTIVE_SECS,
+			TimerMode::Once,
+		)));
+	}
+}
+
+fn restart_failed_level(
+	time: Res<Time>,
+	mut level_failed: ResMut<LevelFailed>,
+	mut commands: Commands,
+) {
+	if level_failed.tick(time.delta()).is_finished() {
+		commands.remove_resource::<LevelFailed>();
+		commands.trigger(RestartLevel);
+	}
+}
+
+/// Picks the pending objective with the lowest order, preferring required objectives over optional ones
 fn update_current_objective(
 	objectives: Query<
-		(Entity, &ObjectiveEntity),
-		(Without<ObjectiveCompleted>, Without<SubObjectiveOf>),
+		(Entity, &ObjectiveEntity, Has<OptionalObjective>),
+		(PendingObjective, Without<SubObjectiveOf>),
 	>,
 	mut current_objective: ResMut<CurrentObjective>,
 ) {
 	let minimum = objectives
 		.iter()
-		.min_by(|(_, a), (_, b)| a.objective_order.total_cmp(&b.objective_order))
-		.map(|(entity, _)| entity);
+		.min_by(|(_, a, a_optional), (_, b, b_optional)| {
+			a_optional
+				.cmp(b_optional)
+				.then(a.objective_order.total_cmp(&b.objective_order))
+		})
+		.map(|(entity, ..)| entity);
 	current_objective.set_if_neq(CurrentObjective(minimum));
 }
 
-/// Marks parent objectives as completed when all their sub-objectives are completed.
+/// Fails pending objectives whose countdown runs out
+fn tick_objective_countdowns(
+	time: Res<Time>,
+	current_objective: Res<CurrentObjective>,
+	mut countdowns: Query<(Entity, &mut ObjectiveCountdown), PendingObjective>,
+	parents: Query<&SubObjectiveOf>,
+	mut commands: Commands,
+) {
+	let Some(current_objective) = **current_objective else {
+		return;
+	};
+	for (entity, mut countdown) in &mut countdowns {
+		let is_current = entity == current_objective
+			|| parents
+				.iter_ancestors(entity)
+				.any(|ancestor| ancestor == current_objective);
+		if !is_current {
+			continue;
+		}
+		if countdown.tick(time.delta()).just_finished() {
+			commands.entity(entity).insert(ObjectiveFailed);
+		}
+	}
+}
+
+/// Marks parent objectives as completed when all their required sub-objectives are completed.
 // TODO (Jondolf): I wanted to handle this with an observer, but had problems where
 //                 siblings of completed sub-objectives were not yet spawned, and thus it
 //                 would incorrectly mark the parent objective as completed.
 fn complete_parent_objectives(
 	new_completed_query: Query<&SubObjectiveOf, Added<ObjectiveCompleted>>,
 	sub_objectives_query: Query<&SubObjectives>,
-	completed_query: Query<(), With<ObjectiveCompleted>>,
+	done_query: Query<(), Or<(With<ObjectiveCompleted>, With<OptionalObjective>)>>,
+	pending_query: Query<(), PendingObjective>,
 	mut commands: Commands,
 ) {
 	for sub_objective_of in new_completed_query.iter() {
-		// Check if all sibling sub-objectives are completed.
-		if let Ok(sub_objectives) = sub_objectives_query.get(sub_objective_of.objective)
-			&& completed_query.iter_many(sub_objectives.iter()).count() == sub_objectives.len()
+		// Check if all sibling sub-objectives are completed. Optional ones don't need to be.
+		if pending_query.contains(sub_objective_of.objective)
+			&& let Ok(sub_objectives) = sub_objectives_query.get(sub_objective_of.objective)
+			&& done_query.iter_many(sub_objectives.iter()).count() == sub_objectives.len()
 		{
 			// Mark the parent objective as completed.
 			commands
@@ -196,3 +404,18 @@ fn complete_parent_objectives(
 		}
 	}
 }
+
+/// Marks parent objectives as failed when one of their required sub-objectives fails.
+fn fail_parent_objectives(
+	new_failed_query: Query<&SubObjectiveOf, (Added<ObjectiveFailed>, Without<OptionalObjective>)>,
+	pending_query: Query<(), PendingObjective>,
+	mut commands: Commands,
+) {
+	for sub_objective_of in new_failed_query.iter() {
+		if pending_query.contains(sub_objective_of.objective) {
+			commands
+				.entity(sub_objective_of.objective)
+				.try_insert(ObjectiveFailed);
+		}
+	}
+}
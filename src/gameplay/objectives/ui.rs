@@ -1,9 +1,10 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, ui::UiSystems};
 
 use crate::{
 	font::VARIABLE_FONT,
 	gameplay::objectives::{
-		CurrentObjective, Objective, ObjectiveCompleted, SubObjectiveOf, SubObjectives,
+		CurrentObjective, Objective, ObjectiveCompleted, ObjectiveCountdown, ObjectiveFailed,
+		OptionalObjective, SubObjectiveOf, SubObjectives,
 	},
 	screens::Screen,
 	theme::{palette::HEADER_TEXT, textures::TexturedUiMaterial},
@@ -11,7 +12,7 @@ use crate::{
 
 pub(super) fn plugin(app: &mut App) {
 	app.add_observer(on_spawn_sub_objective);
-	app.add_observer(on_complete_objective);
+	app.add_observer(on_resolve_objective);
 
 	app.add_systems(OnEnter(Screen::Gameplay), spawn_objective_ui);
 	app.add_systems(
@@ -21,8 +22,14 @@ pub(super) fn plugin(app: &mut App) {
 				//		.run_if(resource_changed::<CurrentObjective>)
 				.after(super::update_current_objective),
 			update_objective_description_ui,
+			despawn_failed_objective_nodes,
 		),
 	);
+	// The objective nodes are respawned through commands, so wait for them before writing the countdowns
+	app.add_systems(
+		PostUpdate,
+		update_objective_countdown_ui.before(UiSystems::Prepare),
+	);
 }
 
 /// The UI node that holds all objectives.
@@ -37,6 +44,17 @@ pub struct ObjectiveNode;
 #[derive(Component, Debug)]
 pub struct SubObjectiveListNode;
 
+/// The text span after an objective's description that shows its [`ObjectiveCountdown`].
+#[derive(Component, Debug)]
+pub struct ObjectiveCountdownText;
+
+/// A failed top-level objective, kept on screen for a moment below the current ones so the failure can be seen.
+#[derive(Component, Debug)]
+pub struct FailedObjectiveNode {
+	objective: Entity,
+	timer: Timer,
+}
+
 /// Links an [`Objective`] to a specific UI node in the world.
 #[derive(Component, Debug)]
 pub struct ObjectiveOfNode {
@@ -100,7 +118,18 @@ pub fn spawn_objective_ui(
 }
 
 /// Creates a UI node for an objective with the given description.
-fn objective_node(description: impl Into<String>, depth: usize) -> impl Bundle {
+/// Optional objectives are tagged as such, and every objective has a span for its countdown.
+fn objective_node(description: impl Into<String>, depth: usize, optional: bool) -> impl Bundle {
+	let font = if depth == 0 {
+		TextFont {
+			font: VARIABLE_FONT,
+			font_size: 16.0,
+			weight: FontWeight(800),
+			..default()
+		}
+	} else {
+		TextFont::from_font_size((16.0 - depth as f32 * 2.0).max(10.0))
+	};
 	(
 		ObjectiveNode,
 		Node {
@@ -111,16 +140,15 @@ fn objective_node(description: impl Into<String>, depth: usize) -> impl Bundle {
 		},
 		children![(
 			Text::new(description),
-			if depth == 0 {
-				TextFont {
-					font: VARIABLE_FONT,
-					font_size: 16.0,
-					weight: FontWeight(800),
-					..default()
-				}
-			} else {
-				TextFont::from_font_size((16.0 - depth as f32 * 2.0).max(10.0))
-			},
+			font.clone(),
+			children![
+				(
+					TextSpan::new(if optional { " (optional)" } else { "" }),
+					font.clone(),
+					TextColor(OBJECTIVE_OPTIONAL_COLOR),
+				),
+				(ObjectiveCountdownText, TextSpan::default(), font),
+			],
 		)],
 	)
 }
@@ -187,11 +215,14 @@ fn spawn_objective(
 	else {
 		return;
 	};
+	let optional = world
+		.entity(objective_entity)
+		.contains::<OptionalObjective>();
 
 	let objective_node_entity = world
 		.spawn((
 			ChildOf(objective_ui),
-			objective_node(objective_description, 0),
+			objective_node(objective_description, 0, optional),
 		))
 		.id();
 
@@ -244,10 +275,13 @@ fn spawn_objective(
 			.get::<Objective>(sub_objective_entity)
 			.map(|o| o.description.clone())
 			.unwrap();
+		let optional = world
+			.entity(sub_objective_entity)
+			.contains::<OptionalObjective>();
 		let objective_node_entity = world
 			.spawn((
 				ChildOf(objective_list_node_entity),
-				objective_node(sub_objective_description, depth),
+				objective_node(sub_objective_description, depth, optional),
 			))
 			.id();
 
@@ -259,29 +293,45 @@ fn spawn_objective(
 	}
 }
 
-const OBJECTIVE_COMPLETED_COLOR: Color = Color::Srgba(Srgba::rgb(0.55, 0.44, 0.53));
-
-/// Updates the objective UI and completes parent objectives when an objective is completed.
-fn on_complete_objective(
-	completed: On<Insert, (ObjectiveCompleted, ObjectiveOfNode)>,
-	objective_node_query: Query<&ObjectiveOfNode, With<ObjectiveCompleted>>,
+pub(crate) const OBJECTIVE_COMPLETED_COLOR: Color = Color::Srgba(Srgba::rgb(0.55, 0.44, 0.53));
+pub(crate) const OBJECTIVE_FAILED_COLOR: Color = Color::Srgba(Srgba::rgb(0.85, 0.25, 0.3));
+pub(crate) const OBJECTIVE_OPTIONAL_COLOR: Color = Color::Srgba(Srgba::rgb(0.7, 0.62, 0.68));
+/// Countdowns with less time left than this are shown in [`OBJECTIVE_FAILED_COLOR`]
+const COUNTDOWN_WARNING_SECS: f32 = 10.0;
+/// How long a failed top-level objective stays on screen
+pub(super) const FAILED_OBJECTIVE_SECS: f32 = 4.0;
+
+/// Updates the objective UI when an objective is completed or failed.
+fn on_resolve_objective(
+	resolved: On<Insert, (ObjectiveCompleted, ObjectiveFailed, ObjectiveOfNode)>,
+	objective_node_query: Query<
+		(&ObjectiveOfNode, Has<ObjectiveFailed>),
+		Or<(With<ObjectiveCompleted>, With<ObjectiveFailed>)>,
+	>,
 	sub_objective_query: Query<&SubObjectiveOf>,
 	child_query: Query<&Children>,
+	objective_ui: Single<&ChildOf, With<ObjectiveUi>>,
+	failed_nodes: Query<&FailedObjectiveNode>,
 	mut commands: Commands,
 ) {
-	// Update the objective UI to show the objective as completed.
-	let Ok(children) = objective_node_query
-		.get(completed.entity)
-		.and_then(|node| child_query.get(node.node))
-	else {
+	// Update the objective UI to show the objective as completed or failed.
+	let Ok((objective_of_node, failed)) = objective_node_query.get(resolved.entity) else {
+		return;
+	};
+	let Ok(children) = child_query.get(objective_of_node.node) else {
 		return;
 	};
 
+	let color = if failed {
+		OBJECTIVE_FAILED_COLOR
+	} else {
+		OBJECTIVE_COMPLETED_COLOR
+	};
 	let text_entity = children[0];
 	commands.entity(text_entity).try_insert((
 		Strikethrough,
-		StrikethroughColor(OBJECTIVE_COMPLETED_COLOR),
-		TextColor(OBJECTIVE_COMPLETED_COLOR),
+		StrikethroughColor(color),
+		TextColor(color),
 	));
 
 	// Remove the sub-objectives from the world.
@@ -289,14 +339,57 @@ fn on_complete_objective(
 		commands.entity(*sub_objective_list).despawn();
 	}
 
-	// If this objective is a top-level objective, despawn its node when completed.
-	if !sub_objective_query.contains(completed.entity)
-		&& let Ok(objective_of_node) = objective_node_query.get(completed.entity)
-	{
+	// Top-level objectives leave the list, failed ones only after a moment
+	if sub_objective_query.contains(resolved.entity) {
+		return;
+	}
+	let already_shown = failed_nodes
+		.iter()
+		.any(|node| node.objective == resolved.entity);
+	if failed && !already_shown {
+		// Moved out of the objective list, which is rebuilt whenever the objectives change
+		commands.entity(objective_of_node.node).try_insert((
+			ChildOf(objective_ui.parent()),
+			FailedObjectiveNode {
+				objective: resolved.entity,
+				timer: Timer::from_seconds(FAILED_OBJECTIVE_SECS, TimerMode::Once),
+			},
+		));
+	} else {
 		commands.entity(objective_of_node.node).try_despawn();
 	}
 }
 
+/// Shows the time left on timed objectives as `m:ss`.
+fn update_objective_countdown_ui(
+	objectives: Query<(&ObjectiveCountdown, &ObjectiveOfNode)>,
+	child_query: Query<&Children>,
+	mut countdown_text_query: Query<(&mut TextSpan, &mut TextColor), With<ObjectiveCountdownText>>,
+) {
+	for (countdown, objective_of_node) in objectives.iter() {
+		let Some(text_entity) = child_query
+			.get(objective_of_node.node)
+			.ok()
+			.and_then(|children| children.first().copied())
+		else {
+			continue;
+		};
+		let mut spans =
+			countdown_text_query.iter_many_mut(child_query.iter_descendants(text_entity));
+		while let Some((mut span, mut color)) = spans.fetch_next() {
+			let remaining = countdown.remaining_secs().ceil() as u32;
+			span.0 = format!("  {}:{:02}", remaining / 60, remaining % 60);
+			color.set_if_neq(TextColor(
+				if countdown.remaining_secs() < COUNTDOWN_WARNING_SECS {
+					OBJECTIVE_FAILED_COLOR
+				} else {
+					Color::WHITE
+				},
+			));
+		}
+	}
+}
+
 /// Updates the objective description UI when an objective's description changes.
 fn update_objective_description_ui(
 	objectives: Query<(&Objective, &ObjectiveOfNode), Changed<Objective>>,
@@ -315,3 +408,15 @@ fn update_objective_description_ui(
 		text.0 = objective.description.clone();
 	}
 }
+
+fn despawn_failed_objective_nodes(
+	mut nodes: Query<(Entity, &mut FailedObjectiveNode)>,
+	time: Res<Time>,
+	mut commands: Commands,
+) {
+	for (entity, mut node) in &mut nodes {
+		if node.timer.tick(time.delta()).is_finished() {
+			commands.entity(entity).despawn();
+		}
+	}
+}
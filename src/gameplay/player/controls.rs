@@ -0,0 +1,360 @@
+//! The player's control settings: rebound inputs, look inversion, gamepad look sensitivity and interaction highlighting.
+//!
+//! [`PlayerInputContext`](super::input::PlayerInputContext) builds its actions from [`ControlSettings`].
+//! On native builds the settings are stored as RON in the user's config directory.
+
+use std::any::TypeId;
+
+use bevy::{
+	platform::collections::HashMap,
+	prelude::*,
+	reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer},
+};
+use bevy_enhanced_input::prelude::*;
+use serde::de::DeserializeSeed as _;
+
+pub(super) fn plugin(app: &mut App) {
+	app.init_resource::<ControlSettings>();
+	app.add_systems(Startup, load_control_settings);
+}
+
+/// How the player's actions are bound. Actions without an entry use their [`ControlAction::default_binding`].
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource)]
+pub(crate) struct ControlSettings {
+	pub(crate) bindings: HashMap<ControlAction, ControlBinding>,
+	/// Flips looking up and down, for both the mouse and the right stick
+	pub(crate) invert_y: bool,
+	/// Multiplies how fast the right stick turns the camera. The mouse uses the camera sensitivity setting.
+	pub(crate) gamepad_look_sensitivity: f32,
+	/// Whether the targeted interactable glows, see [`highlight`](crate::gameplay::interaction::highlight)
+	#[reflect(default = "ControlSettings::default_highlight_interactables")]
+	pub(crate) highlight_interactables: bool,
+}
+
+impl Default for ControlSettings {
+	fn default() -> Self {
+		Self {
+			bindings: HashMap::default(),
+			invert_y: false,
+			gamepad_look_sensitivity: 1.0,
+			highlight_interactables: Self::default_highlight_interactables(),
+		}
+	}
+}
+
+impl ControlSettings {
+	/// Also used for settings saved before the option existed
+	fn default_highlight_interactables() -> bool {
+		true
+	}
+
+	pub(crate) fn binding(&self, action: ControlAction) -> ControlBinding {
+		self.bindings
+			.get(&action)
+			.copied()
+			.unwrap_or_else(|| action.default_binding())
+	}
+
+	/// The bindings to spawn for `action`, see [`Bindings::spawn`]
+	pub(crate) fn bindings_of(&self, action: ControlAction) -> Vec<Binding> {
+		let binding = self.binding(action);
+		binding
+			.keyboard_mouse
+			.map(Binding::from)
+			.into_iter()
+			.chain(binding.gamepad.map(Binding::from))
+			.collect()
+	}
+
+	/// The keyboard binding of a movement direction, or [`Binding::None`] if it's unbound
+	pub(crate) fn movement_binding(&self, action: ControlAction) -> Binding {
+		self.binding(action)
+			.keyboard_mouse
+			.map_or(Binding::None, Binding::from)
+	}
+
+	/// Binds `input` to `action`. Actions that can't share the input with `action` get `action`'s previous input instead.
+	pub(crate) fn rebind(&mut self, action: ControlAction, input: ControlInput) {
+		let previous = self.binding(action).get(input.device());
+		for conflict in self.conflicts(action, input) {
+			let mut conflicting = self.binding(conflict);
+			conflicting.set(input.device(), previous);
+			self.bindings.insert(conflict, conflicting);
+		}
+		let mut binding = self.binding(action);
+		binding.set(input.device(), Some(input));
+		self.bindings.insert(action, binding);
+	}
+
+	pub(crate) fn unbind(&mut self, action: ControlAction, device: ControlDevice) {
+		let mut binding = self.binding(action);
+		binding.set(device, None);
+		self.bindings.insert(action, binding);
+	}
+
+	/// The other actions that use `input` and aren't allowed to share it with `action`
+	pub(crate) fn conflicts(
+		&self,
+		action: ControlAction,
+		input: ControlInput,
+	) -> Vec<ControlAction> {
+		ControlAction::ALL
+			.into_iter()
+			.filter(|&other| {
+				other != action
+					&& !action.may_share_input(other)
+					&& self.binding(other).get(input.device()) == Some(input)
+			})
+			.collect()
+	}
+}
+
+/// The rebindable actions of the player, including the ones of the character controller
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ControlAction {
+	MoveForward,
+	MoveBack,
+	MoveLeft,
+	MoveRight,
+	Jump,
+	Tac,
+	Crane,
+	Mantle,
+	Climbdown,
+	SwimUp,
+	PullObject,
+	DropObject,
+	ThrowObject,
+	Interact,
+	EatObject,
+	VomitObject,
+	CycleVomit,
+	ReachIntoStomach,
+}
+
+impl ControlAction {
+	pub(crate) const ALL: [Self; 18] = [
+		Self::MoveForward,
+		Self::MoveBack,
+		Self::MoveLeft,
+		Self::MoveRight,
+		Self::Jump,
+		Self::Tac,
+		Self::Crane,
+		Self::Mantle,
+		Self::Climbdown,
+		Self::SwimUp,
+		Self::PullObject,
+		Self::DropObject,
+		Self::ThrowObject,
+		Self::Interact,
+		Self::EatObject,
+		Self::VomitObject,
+		Self::CycleVomit,
+		Self::ReachIntoStomach,
+	];
+
+	pub(crate) fn name(self) -> &'static str {
+		match self {
+			Self::MoveForward => "Move Forward",
+			Self::MoveBack => "Move Back",
+			Self::MoveLeft => "Move Left",
+			Self::MoveRight => "Move Right",
+			Self::Jump => "Jump",
+			Self::Tac => "Wall Jump",
+			Self::Crane => "Climb Up",
+			Self::Mantle => "Mantle",
+			Self::Climbdown => "Climb Down",
+			Self::SwimUp => "Swim Up",
+			Self::PullObject => "Pick Up",
+			Self::DropObject => "Drop",
+			Self::ThrowObject => "Throw",
+			Self::Interact => "Interact",
+			Self::EatObject => "Eat",
+			Self::VomitObject => "Vomit",
+			Self::CycleVomit => "Choose What to Vomit",
+			Self::ReachIntoStomach => "Reach Into Stomach",
+		}
+	}
+
+	/// Movement uses the left stick on gamepads, so its directions can only be rebound on the keyboard
+	pub(crate) fn is_movement(self) -> bool {
+		matches!(
+			self,
+			Self::MoveForward | Self::MoveBack | Self::MoveLeft | Self::MoveRight
+		)
+	}
+
+	pub(crate) fn default_binding(self) -> ControlBinding {
+		let key = |key| Some(ControlInput::Key(key));
+		let mouse = |button| Some(ControlInput::Mouse(button));
+		let gamepad = |button| Some(ControlInput::Gamepad(button));
+		let (keyboard_mouse, gamepad) = match self {
+			Self::MoveForward => (key(KeyCode::KeyW), None),
+			Self::MoveBack => (key(KeyCode::KeyS), None),
+			Self::MoveLeft => (key(KeyCode::KeyA), None),
+			Self::MoveRight => (key(KeyCode::KeyD), None),
+			Self::Jump | Self::Tac | Self::Crane | Self::Mantle | Self::SwimUp => {
+				(key(KeyCode::Space), gamepad(GamepadButton::South))
+			}
+			Self::Climbdown => (
+				key(KeyCode::ControlLeft),
+				gamepad(GamepadButton::LeftTrigger2),
+			),
+			Self::PullObject | Self::DropObject => (mouse(MouseButton::Right), None),
+			Self::ThrowObject => (mouse(MouseButton::Left), None),
+			Self::Interact => (mouse(MouseButton::Left), gamepad(GamepadButton::South)),
+			Self::EatObject => (key(KeyCode::KeyE), gamepad(GamepadButton::North)),
+			Self::VomitObject => (mouse(MouseButton::Right), gamepad(GamepadButton::East)),
+			Self::CycleVomit => (key(KeyCode::KeyQ), gamepad(GamepadButton::DPadRight)),
+			Self::ReachIntoStomach => (key(KeyCode::Tab), gamepad(GamepadButton::DPadUp)),
+		};
+		ControlBinding {
+			keyboard_mouse,
+			gamepad,
+		}
+	}
+
+	/// Whether both actions may be bound to the same input.
+	/// Actions of the same kind never get in each other's way, e.g. jumping and mantling or picking up and dropping,
+	/// and interacting only does something while looking at an interactable, so it can share with anything.
+	pub(crate) fn may_share_input(self, other: Self) -> bool {
+		let traversal = |action: Self| {
+			matches!(
+				action,
+				Self::Jump | Self::Tac | Self::Crane | Self::Mantle | Self::SwimUp
+			)
+		};
+		let hands = |action: Self| {
+			matches!(
+				action,
+				Self::PullObject | Self::DropObject | Self::ThrowObject | Self::VomitObject
+			)
+		};
+		self == Self::Interact
+			|| other == Self::Interact
+			|| (traversal(self) && traversal(other))
+			|| (hands(self) && hands(other))
+	}
+}
+
+/// The inputs bound to one [`ControlAction`], at most one per device
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ControlBinding {
+	pub(crate) keyboard_mouse: Option<ControlInput>,
+	pub(crate) gamepad: Option<ControlInput>,
+}
+
+impl ControlBinding {
+	pub(crate) fn get(self, device: ControlDevice) -> Option<ControlInput> {
+		match device {
+			ControlDevice::KeyboardMouse => self.keyboard_mouse,
+			ControlDevice::Gamepad => self.gamepad,
+		}
+	}
+
+	fn set(&mut self, device: ControlDevice, input: Option<ControlInput>) {
+		match device {
+			ControlDevice::KeyboardMouse => self.keyboard_mouse = input,
+			ControlDevice::Gamepad => self.gamepad = input,
+		}
+	}
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ControlDevice {
+	KeyboardMouse,
+	Gamepad,
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ControlInput {
+	Key(KeyCode),
+	Mouse(MouseButton),
+	Gamepad(GamepadButton),
+}
+
+impl ControlInput {
+	pub(crate) fn device(self) -> ControlDevice {
+		match self {
+			Self::Key(_) | Self::Mouse(_) => ControlDevice::KeyboardMouse,
+			Self::Gamepad(_) => ControlDevice::Gamepad,
+		}
+	}
+}
+
+impl From<ControlInput> for Binding {
+	fn from(input: ControlInput) -> Self {
+		match input {
+			ControlInput::Key(key) => key.into(),
+			ControlInput::Mouse(button) => button.into(),
+			ControlInput::Gamepad(button) => button.into(),
+		}
+	}
+}
+
+fn load_control_settings(registry: Res<AppTypeRegistry>, mut settings: ResMut<ControlSettings>) {
+	let Some(path) = settings_path() else {
+		return;
+	};
+	let Ok(text) = std::fs::read_to_string(&path) else {
+		return;
+	};
+	let registry = registry.read();
+	let Some(registration) = registry.get(TypeId::of::<ControlSettings>()) else {
+		return;
+	};
+	let loaded = ron::Deserializer::from_str(&text)
+		.map_err(BevyError::from)
+		.and_then(|mut deserializer| {
+			Ok(TypedReflectDeserializer::new(registration, &registry)
+				.deserialize(&mut deserializer)?)
+		})
+		.map(|reflected| ControlSettings::from_reflect(&*reflected));
+	match loaded {
+		Ok(Some(loaded)) => *settings = loaded,
+		Ok(None) => warn!("Failed to read controls from {}", path.display()),
+		Err(err) => warn!("Failed to read controls from {}: {err}", path.display()),
+	}
+}
+
+/// Writes the settings to disk, so they are loaded on the next start
+pub(crate) fn save_control_settings(
+	settings: Res<ControlSettings>,
+	registry: Res<AppTypeRegistry>,
+) {
+	let Some(path) = settings_path() else {
+		return;
+	};
+	let registry = registry.read();
+	let serializer = TypedReflectSerializer::new(settings.as_partial_reflect(), &registry);
+	let result = ron::ser::to_string_pretty(&serializer, default())
+		.map_err(BevyError::from)
+		.and_then(|text| {
+			if let Some(dir) = path.parent() {
+				std::fs::create_dir_all(dir)?;
+			}
+			Ok(std::fs::write(&path, text)?)
+		});
+	if let Err(err) = result {
+		warn!("Failed to save controls to {}: {err}", path.display());
+	}
+}
+
+/// Where the settings are stored, following each platform's convention for config files.
+/// The web has no file system, so there the settings only last for the session.
+fn settings_path() -> Option<std::path::PathBuf> {
+	if cfg!(target_family = "wasm") {
+		return None;
+	}
+	let env_dir = |name| std::env::var_os(name).map(std::path::PathBuf::from);
+	let config_dir = if cfg!(windows) {
+		env_dir("APPDATA")
+	} else if cfg!(target_os = "macos") {
+		env_dir("HOME").map(|home| home.join("Library/Application Support"))
+	} else {
+		env_dir("XDG_CONFIG_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".config")))
+	}?;
+	Some(config_dir.join(env!("CARGO_PKG_NAME")).join("controls.ron"))
+}
@@ -5,10 +5,8 @@ use bevy::prelude::*;
 use bevy_yarnspinner::prelude::*;
 
 use crate::{
-	PostPhysicsAppSystems,
-	gameplay::interaction::InteractEvent,
-	props::{interactables::InteractableEntity, logic_entity::YarnNode},
-	screens::Screen,
+	PostPhysicsAppSystems, gameplay::interaction::InteractEvent, props::logic_entity::YarnNode,
+	screens::Screen, third_party::bevy_yarnspinner::ConversationRunner,
 };
 
 use super::camera::PlayerCameraParent;
@@ -47,12 +45,12 @@ pub(crate) struct DialogueSpeaker(pub(crate) Option<Entity>);
 
 fn interact_with_dialogue(
 	trigger: On<InteractEvent>,
-	q_yarn_node: Query<(&YarnNode, Option<&InteractableEntity>)>,
-	mut dialogue_runner: Single<&mut DialogueRunner>,
+	q_yarn_node: Query<&YarnNode>,
+	mut dialogue_runner: Single<&mut DialogueRunner, With<ConversationRunner>>,
 	mut speaker: ResMut<DialogueSpeaker>,
 ) {
-	if let Ok((node, interactable)) = q_yarn_node.get(trigger.0) {
-		if interactable.is_some_and(|i| i.is_edible) {
+	if let Ok(node) = q_yarn_node.get(trigger.0) {
+		if node.is_non_dialogue {
 			return;
 		}
 		if dialogue_runner.try_start_node(&node.yarn_node).is_ok() {
@@ -65,7 +63,7 @@ fn interact_with_dialogue(
 fn stop_dialogue_far_from_speaker(
 	player: Single<&GlobalTransform, With<PlayerCameraParent>>,
 	transforms: Query<&GlobalTransform>,
-	mut dialogue_runner: Single<&mut DialogueRunner>,
+	mut dialogue_runner: Single<&mut DialogueRunner, With<ConversationRunner>>,
 	speaker: Res<DialogueSpeaker>,
 ) {
 	const MAX_DIALOGUE_DISTANCE: f32 = 4.0;
@@ -3,14 +3,17 @@
 use std::any::TypeId;
 
 use bevy::{
-	ecs::{lifecycle::HookContext, world::DeferredWorld},
+	ecs::{lifecycle::HookContext, spawn::SpawnIter, world::DeferredWorld},
 	platform::collections::HashSet,
 	prelude::*,
 };
 use bevy_ahoy::prelude::*;
 use bevy_enhanced_input::prelude::{Press, *};
 
-use super::Player;
+use super::{
+	Player,
+	controls::{ControlAction, ControlSettings},
+};
 
 pub(super) fn plugin(app: &mut App) {
 	app.add_input_context::<PlayerInputContext>();
@@ -18,10 +21,14 @@ pub(super) fn plugin(app: &mut App) {
 	app.init_resource::<BlocksInput>();
 	app.add_systems(
 		PreUpdate,
-		update_player_input_binding.run_if(resource_changed::<BlocksInput>),
+		(
+			update_player_input_binding.run_if(resource_changed::<BlocksInput>),
+			rebind_player_input.run_if(resource_changed::<ControlSettings>),
+		),
 	);
 }
 
+/// Fires for as long as the button is held, so interactions can require holding it
 #[derive(Debug, InputAction)]
 #[action_output(bool)]
 pub(crate) struct Interact;
@@ -30,10 +37,21 @@ pub(crate) struct Interact;
 #[action_output(bool)]
 pub(crate) struct EatObject;
 
+/// Fires for as long as the button is held, the longer the further the vomit is thrown
 #[derive(Debug, InputAction)]
 #[action_output(bool)]
 pub(crate) struct VomitObject;
 
+/// Selects the next content of the stomach to vomit
+#[derive(Debug, InputAction)]
+#[action_output(bool)]
+pub(crate) struct CycleVomit;
+
+/// Frees the cursor to poke around in the stomach view, see [`crate::gameplay::stomach::reach`]
+#[derive(Debug, InputAction)]
+#[action_output(bool)]
+pub(crate) struct ReachIntoStomach;
+
 #[derive(Debug, Component, Default)]
 #[component(on_add = PlayerInputContext::on_add)]
 pub(crate) struct PlayerInputContext;
@@ -44,6 +62,13 @@ pub(crate) struct BlocksInput(HashSet<TypeId>);
 
 impl PlayerInputContext {
 	fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+		let settings = world.resource::<ControlSettings>().clone();
+		let bindings =
+			|action| Bindings::spawn(SpawnIter(settings.bindings_of(action).into_iter()));
+		let look_negate = Negate {
+			y: settings.invert_y,
+			..Negate::none()
+		};
 		world
 			.commands()
 			.entity(ctx.entity)
@@ -53,7 +78,12 @@ impl PlayerInputContext {
 					ActionSettings { consume_input: false, ..default() },
 					DeadZone::default(),
 					Bindings::spawn((
-						Cardinal::wasd_keys(),
+						Cardinal::new(
+							settings.movement_binding(ControlAction::MoveForward),
+							settings.movement_binding(ControlAction::MoveLeft),
+							settings.movement_binding(ControlAction::MoveBack),
+							settings.movement_binding(ControlAction::MoveRight),
+						),
 						Axial::left_stick()
 					))
 				),
@@ -61,42 +91,30 @@ impl PlayerInputContext {
 					Action::<Jump>::new(),
 					ActionSettings { consume_input: false, ..default() },
 					Press::default(),
-					bindings![
-						KeyCode::Space,
-						GamepadButton::South,
-					],
+					bindings(ControlAction::Jump),
 				),
 				(
 					Action::<Tac>::new(),
 					ActionSettings { consume_input: false, ..default() },
 					Press::default(),
-					bindings![
-						KeyCode::Space,
-						GamepadButton::South,
-					],
+					bindings(ControlAction::Tac),
 				),
 				(
 					Action::<Crane>::new(),
 					ActionSettings { consume_input: false, ..default() },
 					Press::default(),
-					bindings![
-						KeyCode::Space,
-						GamepadButton::South,
-					],
+					bindings(ControlAction::Crane),
 				),
 				(
 					Action::<Mantle>::new(),
 					ActionSettings { consume_input: false, ..default() },
 					Hold::new(0.2),
-					bindings![
-						KeyCode::Space,
-						GamepadButton::South,
-					],
+					bindings(ControlAction::Mantle),
 				),
 				(
 					Action::<Climbdown>::new(),
 					ActionSettings { consume_input: false, ..default() },
-					bindings![KeyCode::ControlLeft, GamepadButton::LeftTrigger2],
+					bindings(ControlAction::Climbdown),
 				),
 				/*
 				(
@@ -108,47 +126,62 @@ impl PlayerInputContext {
 				(
 					Action::<SwimUp>::new(),
 					ActionSettings { consume_input: false, ..default() },
-					bindings![KeyCode::Space, GamepadButton::South],
+					bindings(ControlAction::SwimUp),
 				),
 				(
 					Action::<PullObject>::new(),
 					ActionSettings { consume_input: true, ..default() },
 					Press::default(),
-					bindings![MouseButton::Right],
+					bindings(ControlAction::PullObject),
 				),
 				(
 					Action::<DropObject>::new(),
 					ActionSettings { consume_input: true, ..default() },
 					Press::default(),
-					bindings![MouseButton::Right],
+					bindings(ControlAction::DropObject),
 				),
 				(
 					Action::<ThrowObject>::new(),
 					ActionSettings { consume_input: true, ..default() },
 					Press::default(),
-					bindings![MouseButton::Left],
+					bindings(ControlAction::ThrowObject),
 				),
 				(
 					Action::<RotateCamera>::new(),
 					ActionSettings { consume_input: false, ..default() },
 
 					Bindings::spawn((
-						Spawn((Binding::mouse_motion(), Scale::splat(0.07))),
-						Axial::right_stick().with((Scale::splat(4.0),  DeadZone::default())),
+						Spawn((Binding::mouse_motion(), Scale::splat(0.07), look_negate)),
+						Axial::right_stick().with((
+							Scale::splat(4.0 * settings.gamepad_look_sensitivity),
+							look_negate,
+							DeadZone::default(),
+						)),
 					))
 				),
 				(
 					Action::<Interact>::new(),
-					Press::default(),
-					bindings![MouseButton::Left, GamepadButton::South]
+					bindings(ControlAction::Interact)
 				),
 				(
 					Action::<EatObject>::new(),
-					bindings![KeyCode::KeyE, GamepadButton::North]
+					bindings(ControlAction::EatObject)
 				),
 				(
 					Action::<VomitObject>::new(),
-					bindings![MouseButton::Right, GamepadButton::East]
+					bindings(ControlAction::VomitObject)
+				),
+				(
+					Action::<CycleVomit>::new(),
+					Press::default(),
+					bindings(ControlAction::CycleVomit)
+				),
+				(
+					Action::<ReachIntoStomach>::new(),
+					// Letting go of the stomach with the same button must not reach right back in
+					ActionSettings { require_reset: true, ..default() },
+					Press::default(),
+					bindings(ControlAction::ReachIntoStomach)
 				)
 			]));
 	}
@@ -168,3 +201,15 @@ fn update_player_input_binding(
 			.despawn_related::<Actions<PlayerInputContext>>();
 	}
 }
+
+/// Respawns the actions with the new bindings, unless input is blocked, in which case they are respawned once it is unblocked
+fn rebind_player_input(
+	player: Single<Entity, (With<Player>, With<PlayerInputContext>)>,
+	mut commands: Commands,
+) {
+	commands
+		.entity(*player)
+		.remove_with_requires::<PlayerInputContext>()
+		.despawn_related::<Actions<PlayerInputContext>>()
+		.insert(PlayerInputContext);
+}
@@ -17,6 +17,7 @@ use crate::third_party::avian3d::CollisionLayer;
 
 pub(crate) mod assets;
 pub(crate) mod camera;
+pub(crate) mod controls;
 pub(crate) mod dialogue;
 pub(crate) mod input;
 pub(crate) mod movement_sound;
@@ -26,6 +27,7 @@ pub(super) fn plugin(app: &mut App) {
 	app.add_plugins((
 		assets::plugin,
 		camera::plugin,
+		controls::plugin,
 		input::plugin,
 		dialogue::plugin,
 		movement_sound::plugin,
@@ -43,6 +45,8 @@ pub(crate) const PLAYER_RADIUS: f32 = 0.225;
 // Height of 1.75 meters
 const PLAYER_HEIGHT: f32 = 1.75;
 const PLAYER_HALF_HEIGHT: f32 = PLAYER_HEIGHT / 2.0;
+/// Walking speed in meters per second, before any effects
+pub(crate) const PLAYER_SPEED: f32 = 5.0;
 
 fn setup_player(
 	add: On<Add, Player>,
@@ -54,7 +58,7 @@ fn setup_player(
 		PlayerInputContext,
 		Collider::cylinder(PLAYER_RADIUS, PLAYER_HEIGHT),
 		CharacterController {
-			speed: 5.0,
+			speed: PLAYER_SPEED,
 			friction_hz: 15.0,
 			filter: SpatialQueryFilter::DEFAULT
 				.with_mask(LayerMask::ALL & !CollisionLayer::Stomach.to_bits()),
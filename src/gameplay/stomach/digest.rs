@@ -0,0 +1,192 @@
+//! Digestion of the stomach's contents.
+//!
+//! Eaten props shrink over a time that scales with their mass until they are consumed and despawned.
+//! The stomach only fits [`Stomach::capacity`] worth of props, measured by their collider AABBs.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::gameplay::stomach::{
+	Stomach,
+	eat::{Eat, shrink_to_fit},
+	vomit::{RequestVomit, Vomit},
+};
+
+pub(super) fn plugin(app: &mut App) {
+	app.add_observer(start_digesting)
+		.add_observer(stop_digesting);
+	app.add_systems(Update, (digest, vomit_when_overfull).chain());
+}
+
+/// How long to wait between throwing up props while the stomach is overfull, in seconds.
+/// Also keeps the stomach from retrying every frame while there's no room to vomit.
+const OVERFULL_VOMIT_INTERVAL_SECS: f32 = 0.5;
+
+/// A prop in the stomach that is being digested
+#[derive(Component, Debug)]
+pub(crate) struct Digesting {
+	pub(crate) timer: Timer,
+	/// The volume of the prop before it started shrinking, in cubic meters
+	pub(crate) volume: f32,
+	original_scale: Vec3,
+	/// How much the prop was scaled down to fit into the stomach, see [`shrink_to_fit`]
+	fit: f32,
+}
+
+impl Digesting {
+	/// How much the prop is scaled down by now, both to fit into the stomach and from being digested
+	pub(crate) fn scale_factor(&self) -> f32 {
+		self.fit * (1.0 - (1.0 - DIGESTED_SCALE) * self.timer.fraction())
+	}
+
+	/// The volume the prop still takes up in the stomach, in cubic meters
+	pub(crate) fn remaining_volume(&self) -> f32 {
+		self.volume * self.scale_factor().powi(3)
+	}
+}
+
+/// Event triggered when a prop has been fully digested, right before it is despawned.
+#[derive(EntityEvent, Debug)]
+pub struct Digested {
+	/// The rigid body entity that was digested.
+	#[event_target]
+	pub body: Entity,
+}
+
+/// How long a kilogram of prop takes to digest
+const DIGEST_SECONDS_PER_KG: f32 = 6.0;
+/// Even the lightest snack takes a moment, and the heaviest one is gone eventually
+const DIGEST_SECONDS_RANGE: std::ops::RangeInclusive<f32> = 5.0..=120.0;
+/// How small a prop gets right before it is consumed, relative to its original size
+const DIGESTED_SCALE: f32 = 0.2;
+
+/// The solid colliders of props, leaving out sensors like the one for temperature,
+/// which would make every prop as large as the sensor
+pub(crate) type PropColliders<'w, 's> =
+	Query<'w, 's, (&'static ColliderOf, &'static ColliderAabb), Without<Sensor>>;
+
+/// The AABB around all colliders of `body`
+fn prop_aabb(
+	body: Entity,
+	children: &Query<&Children>,
+	colliders: &PropColliders,
+) -> Option<ColliderAabb> {
+	colliders
+		.iter_many(std::iter::once(body).chain(children.iter_descendants(body)))
+		.filter(|(collider_of, _)| collider_of.body == body)
+		.map(|(_, aabb)| *aabb)
+		.reduce(|a, b| a.merged(b))
+}
+
+/// The volume of the AABB around all colliders of `body`, in cubic meters
+pub(crate) fn prop_volume(
+	body: Entity,
+	children: &Query<&Children>,
+	colliders: &PropColliders,
+) -> f32 {
+	prop_aabb(body, children, colliders).map_or(0.0, |aabb| {
+		let size = aabb.size();
+		size.x * size.y * size.z
+	})
+}
+
+/// The longest side of the AABB around all colliders of `body`, in meters
+pub(crate) fn prop_size(
+	body: Entity,
+	children: &Query<&Children>,
+	colliders: &PropColliders,
+) -> f32 {
+	prop_aabb(body, children, colliders).map_or(0.0, |aabb| aabb.size().max_element())
+}
+
+/// How much of [`Stomach::capacity`] the contents take up, in cubic meters
+pub(crate) fn stomach_fill(stomach: &Stomach, digesting: &Query<&Digesting>) -> f32 {
+	digesting
+		.iter_many(&stomach.contents)
+		.map(Digesting::remaining_volume)
+		.sum()
+}
+
+fn start_digesting(
+	eat: On<Eat>,
+	mut bodies: Query<(&mut Transform, Option<&ComputedMass>), Without<Digesting>>,
+	children: Query<&Children>,
+	colliders: PropColliders,
+	mut commands: Commands,
+) {
+	let Ok((mut transform, mass)) = bodies.get_mut(eat.body) else {
+		return;
+	};
+	let original_scale = transform.scale;
+	// Shrink right away, so a large prop doesn't get wedged between the walls first
+	let fit = shrink_to_fit(prop_size(eat.body, &children, &colliders));
+	transform.scale = original_scale * fit;
+
+	let mass = mass.map_or(1.0, |mass| mass.value());
+	let seconds = (DIGEST_SECONDS_PER_KG * mass)
+		.clamp(*DIGEST_SECONDS_RANGE.start(), *DIGEST_SECONDS_RANGE.end());
+	commands.entity(eat.body).insert(Digesting {
+		timer: Timer::from_seconds(seconds, TimerMode::Once),
+		volume: prop_volume(eat.body, &children, &colliders),
+		original_scale,
+		fit,
+	});
+}
+
+/// Vomited props come out whole again
+fn stop_digesting(
+	vomit: On<Vomit>,
+	mut bodies: Query<(&Digesting, &mut Transform)>,
+	mut commands: Commands,
+) {
+	let Ok((digesting, mut transform)) = bodies.get_mut(vomit.body) else {
+		return;
+	};
+	transform.scale = digesting.original_scale;
+	commands.entity(vomit.body).remove::<Digesting>();
+}
+
+fn digest(
+	mut stomach: Single<&mut Stomach>,
+	mut bodies: Query<(Entity, &mut Digesting, &mut Transform)>,
+	time: Res<Time>,
+	mut commands: Commands,
+) {
+	let mut digested = Vec::new();
+	let mut contents = bodies.iter_many_mut(&stomach.contents);
+	while let Some((body, mut digesting, mut transform)) = contents.fetch_next() {
+		digesting.timer.tick(time.delta());
+		transform.scale = digesting.original_scale * digesting.scale_factor();
+		if digesting.timer.is_finished() {
+			digested.push(body);
+		}
+	}
+
+	for body in digested {
+		stomach.contents.remove(&body);
+		commands.trigger(Digested { body });
+		commands.entity(body).despawn();
+	}
+}
+
+/// Throws up the top of the contents until they fit again, e.g. after eating something huge
+fn vomit_when_overfull(
+	stomach: Single<&Stomach>,
+	digesting: Query<&Digesting>,
+	time: Res<Time>,
+	mut cooldown: Local<Option<Timer>>,
+	mut commands: Commands,
+) {
+	if let Some(cooldown) = cooldown.as_mut()
+		&& !cooldown.tick(time.delta()).is_finished()
+	{
+		return;
+	}
+	if stomach_fill(&stomach, &digesting) > stomach.capacity {
+		commands.trigger(RequestVomit::top());
+		*cooldown = Some(Timer::from_seconds(
+			OVERFULL_VOMIT_INTERVAL_SECS,
+			TimerMode::Once,
+		));
+	}
+}
@@ -1,5 +1,7 @@
 use avian3d::prelude::*;
 use bevy::{camera::visibility::RenderLayers, prelude::*};
+use bevy_enhanced_input::prelude::{Action, ActionState};
+use bevy_landmass::{Character, prelude::*};
 use bevy_seedling::sample::{AudioSample, RandomPitch, SamplePlayer};
 use bevy_shuffle_bag::ShuffleBag;
 
@@ -7,8 +9,13 @@ use crate::{
 	RenderLayer,
 	audio::SfxPool,
 	gameplay::{
-		interaction::InteractEvent,
-		stomach::{EdibleProp, Stomach},
+		interaction::{AvailableInteraction, EatEvent},
+		player::input::EatObject,
+		stomach::{
+			EdibleProp, Stomach,
+			digest::{Digesting, PropColliders, prop_size, stomach_fill},
+			snapshot::{EatenSnapshot, SnapshotBodyQuery, SnapshotEntityQuery},
+		},
 	},
 	third_party::avian3d::CollisionLayer,
 };
@@ -19,8 +26,68 @@ pub(super) fn plugin(app: &mut App) {
 
 	app.init_resource::<EatSounds>()
 		.init_resource::<GulpTimer>()
-		.add_systems(Update, gulp)
-		.add_observer(play_eat_sound);
+		.add_systems(
+			Update,
+			(gulp, swallow.run_if(resource_exists::<Swallowing>)),
+		)
+		.add_observer(play_eat_sound)
+		.add_observer(play_refused_sound);
+}
+
+/// Event triggered when the player tries to eat something that doesn't fit.
+#[derive(EntityEvent, Debug)]
+pub struct EatRefused {
+	/// The rigid body entity that didn't fit.
+	#[event_target]
+	pub body: Entity,
+	/// Why it wasn't eaten.
+	pub reason: EatRefusal,
+}
+
+/// Why something couldn't be eaten
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EatRefusal {
+	/// The stomach is already full
+	TooFull,
+	/// The prop is larger than [`SWALLOW_MAX_SIZE`]
+	TooBig,
+}
+
+impl EatRefusal {
+	/// What to tell the player
+	pub fn message(self) -> &'static str {
+		match self {
+			Self::TooFull => "Too full to eat anything else",
+			Self::TooBig => "Too big to swallow",
+		}
+	}
+}
+
+/// [`Resource`] present while the player swallows a prop larger than [`SWALLOW_SLOW_SIZE`].
+/// Nothing else can be eaten in the meantime, and letting go of the eat button or looking away stops the swallowing.
+#[derive(Resource, Debug)]
+pub(crate) struct Swallowing {
+	body: Entity,
+	timer: Timer,
+	gulp_timer: Timer,
+}
+
+/// Props with a longer side than this take a while to swallow and get shrunk down to it inside the stomach, in meters
+const SWALLOW_SLOW_SIZE: f32 = 0.6;
+/// Props with a longer side than this can't be eaten at all, in meters
+const SWALLOW_MAX_SIZE: f32 = 2.0;
+/// How long swallowing takes per meter above [`SWALLOW_SLOW_SIZE`]
+const SWALLOW_SECONDS_PER_METER: f32 = 2.5;
+/// Time between the gulps while swallowing something large
+const SWALLOW_GULP_INTERVAL_SECONDS: f32 = 0.45;
+
+/// How much a prop of `size` is scaled down inside the stomach, so large props don't get crammed in
+pub(crate) fn shrink_to_fit(size: f32) -> f32 {
+	if size > SWALLOW_SLOW_SIZE {
+		SWALLOW_SLOW_SIZE / size
+	} else {
+		1.0
+	}
 }
 
 /// Event for eating an entity and putting it into the stomach.
@@ -31,10 +98,11 @@ pub struct Eat {
 	pub body: Entity,
 }
 
-fn on_eat(
+pub(super) fn on_eat(
 	eat: On<Eat>,
 	mut transform_query: Query<&mut Transform>,
-	mut layer_query: Query<(Option<&CollisionLayers>, Has<Mesh3d>)>,
+	body_query: SnapshotBodyQuery,
+	entity_query: SnapshotEntityQuery,
 	child_query: Query<&Children>,
 	stomach: Single<(&mut Stomach, &GlobalTransform)>,
 	mut commands: Commands,
@@ -50,41 +118,116 @@ fn on_eat(
 	transform.translation = stomach_transform.translation();
 	stomach.contents.insert(eat.body);
 
-	// Lock the entity's Z translation.
-	// TODO: Don't overwrite any other locked axes.
+	// Remember how the entity was, so vomiting it can restore it exactly.
+	let snapshot = EatenSnapshot::take(eat.body, &body_query, &entity_query, &child_query);
+
+	// Lock the entity's Z translation, on top of whatever was locked already.
+	// It has to move around in the stomach, even if its physics or navigation were disabled.
 	commands
 		.entity(eat.body)
-		.insert(LockedAxes::default().lock_translation_z());
+		.insert(
+			snapshot
+				.locked_axes
+				.unwrap_or_default()
+				.lock_translation_z(),
+		)
+		.remove::<RigidBodyDisabled>();
 
 	// Change the collision and render layers to the stomach layers.
-	for entity in std::iter::once(eat.body).chain(child_query.iter_descendants(eat.body)) {
-		let Ok((collision_layers, has_mesh)) = layer_query.get_mut(entity) else {
-			continue;
-		};
-
-		if let Some(collision_layers) = collision_layers {
-			let mut new_layers = *collision_layers;
+	for entity in &snapshot.entities {
+		let mut entity_commands = commands.entity(entity.entity);
+		if let Some(collision_layers) = entity.collision_layers {
+			let mut new_layers = collision_layers;
 			new_layers.memberships.add(CollisionLayer::Stomach);
 			new_layers.filters.add(CollisionLayer::Stomach);
-			commands.entity(entity).insert(new_layers);
+			entity_commands.insert(new_layers);
+		}
+
+		if entity.has_mesh {
+			entity_commands.insert(RenderLayers::from(RenderLayer::STOMACH));
 		}
 
-		if has_mesh {
-			commands
-				.entity(entity)
-				.insert(RenderLayers::from(RenderLayer::STOMACH));
+		if entity.collider_disabled {
+			entity_commands.remove::<ColliderDisabled>();
+		}
+
+		if entity.character.is_some() {
+			entity_commands.remove::<(Character<ThreeD>, CharacterSettings, ArchipelagoRef3d)>();
 		}
 	}
+
+	commands.entity(eat.body).insert(snapshot);
 }
 
+/// Eats the prop, unless it's too big or the stomach is already full. Large props take a while to swallow.
+/// Something that doesn't fit into what's left still goes down, but is thrown back up right away.
 fn try_eat(
-	interaction: On<InteractEvent>,
+	interaction: On<EatEvent>,
 	eidble_query: Query<(), With<EdibleProp>>,
+	stomach: Single<&Stomach>,
+	digesting: Query<&Digesting>,
+	children: Query<&Children>,
+	colliders: PropColliders,
+	swallowing: Option<Res<Swallowing>>,
 	mut commands: Commands,
 ) {
-	if eidble_query.contains(interaction.0) {
+	let body = interaction.0;
+	if !eidble_query.contains(body) || swallowing.is_some() {
+		return;
+	}
+
+	let size = prop_size(body, &children, &colliders);
+	let refusal = if size > SWALLOW_MAX_SIZE {
+		Some(EatRefusal::TooBig)
+	} else if stomach_fill(&stomach, &digesting) >= stomach.capacity {
+		Some(EatRefusal::TooFull)
+	} else {
+		None
+	};
+	if let Some(reason) = refusal {
+		commands.trigger(EatRefused { body, reason });
+		return;
+	}
+
+	if size > SWALLOW_SLOW_SIZE {
+		let seconds = (size - SWALLOW_SLOW_SIZE) * SWALLOW_SECONDS_PER_METER;
+		commands.insert_resource(Swallowing {
+			body,
+			timer: Timer::from_seconds(seconds, TimerMode::Once),
+			gulp_timer: Timer::from_seconds(SWALLOW_GULP_INTERVAL_SECONDS, TimerMode::Repeating),
+		});
+		return;
+	}
+	commands.trigger(Eat { body });
+}
+
+/// Gulps away at a large prop until it's down, as long as the player keeps holding the eat button on it
+fn swallow(
+	mut swallowing: ResMut<Swallowing>,
+	bodies: Query<(), With<EdibleProp>>,
+	available: Res<AvailableInteraction>,
+	eat_action: Query<&ActionState, With<Action<EatObject>>>,
+	mut sounds: ResMut<EatSounds>,
+	time: Res<Time>,
+	mut commands: Commands,
+) {
+	let still_held = eat_action.iter().any(|state| *state == ActionState::Fired);
+	// Despawned or no longer edible in the meantime, or the player let go or looked away
+	if !bodies.contains(swallowing.body)
+		|| !still_held
+		|| available.target_entity != Some(swallowing.body)
+	{
+		commands.remove_resource::<Swallowing>();
+		return;
+	}
+
+	if swallowing.gulp_timer.tick(time.delta()).just_finished() {
+		commands.spawn(sounds.sample());
+	}
+	if swallowing.timer.tick(time.delta()).is_finished() {
+		commands.remove_resource::<Swallowing>();
 		commands.trigger(Eat {
-			body: interaction.0,
+			body: swallowing.body,
 		});
 	}
 }
@@ -92,6 +235,18 @@ fn try_eat(
 #[derive(Resource)]
 struct EatSounds(ShuffleBag<Handle<AudioSample>>);
 
+impl EatSounds {
+	/// The next eating sound to spawn
+	fn sample(&mut self) -> impl Bundle + use<> {
+		let rng = &mut rand::rng();
+		(
+			SamplePlayer::new(self.0.pick(rng).clone()),
+			RandomPitch(1.05..1.25),
+			SfxPool,
+		)
+	}
+}
+
 impl FromWorld for EatSounds {
 	fn from_world(world: &mut World) -> Self {
 		let assets = world.resource::<AssetServer>();
@@ -117,16 +272,9 @@ fn play_eat_sound(
 	mut sounds: ResMut<EatSounds>,
 	mut commands: Commands,
 ) {
-	let rng = &mut rand::rng();
-	let sound = sounds.0.pick(rng);
-
 	gulp.timer = Some(Timer::from_seconds(0.5, TimerMode::Once));
 
-	commands.spawn((
-		SamplePlayer::new(sound.clone()),
-		RandomPitch(1.05..1.25),
-		SfxPool,
-	));
+	commands.spawn(sounds.sample());
 }
 
 #[derive(Resource)]
@@ -160,3 +308,12 @@ fn gulp(mut gulp: ResMut<GulpTimer>, time: Res<Time>, mut commands: Commands) {
 		));
 	}
 }
+
+/// A low, strained gulp when nothing more fits in
+fn play_refused_sound(_: On<EatRefused>, gulp: Res<GulpTimer>, mut commands: Commands) {
+	commands.spawn((
+		SamplePlayer::new(gulp.gulp.clone()),
+		RandomPitch(0.6..0.7),
+		SfxPool,
+	));
+}
@@ -0,0 +1,170 @@
+//! Effects an eaten prop has on the player while it digests, authored in TrenchBroom.
+//!
+//! Effects start when the prop is eaten and expire once it has been digested or vomited back up.
+
+use bevy::prelude::*;
+use bevy_ahoy::CharacterController;
+use bevy_trenchbroom::prelude::*;
+
+use crate::gameplay::{
+	core::{FeverSource, FeverSourceOf, Health, Temperature},
+	fever::postprocess::FeverPostProcessSettings,
+	player::{PLAYER_SPEED, Player},
+	stomach::{
+		Stomach,
+		digest::{Digested, Digesting},
+		eat::Eat,
+		vomit::Vomit,
+	},
+};
+
+pub(super) fn plugin(app: &mut App) {
+	app.add_observer(start_effect)
+		.add_observer(expire_effect_on_digested)
+		.add_observer(expire_effect_on_vomit);
+	app.add_systems(Update, apply_effects);
+}
+
+/// Trenchbroom component for what eating an [`InteractableEntity`](crate::props::interactables::InteractableEntity) does to the player.
+/// Only has an effect if the entity is edible.
+///
+/// When several eaten props digest at once, healing and temperature changes add up,
+/// speed multipliers multiply and the strongest hallucination wins.
+#[base_class]
+#[derive(Clone)]
+#[component(immutable)]
+pub struct DigestEffect {
+	/// How much health is restored over the whole digestion.
+	pub heal: f32,
+	/// How many degrees the player's temperature changes over the whole digestion. Negative values cool down.
+	pub temperature_change: f32,
+	/// Adds a fever source with this rate while digesting, e.g. `1.02` raises the temperature by 2% per tick. `0` for none.
+	pub fever_rate: f32,
+	/// Whether eating this gets rid of the fever sources that other eaten props added.
+	/// The player's own fever is left alone.
+	pub cures_fever: bool,
+	/// Multiplies the player's movement speed while digesting.
+	pub speed_multiplier: f32,
+	/// How strongly the player hallucinates while digesting, from `0` to `1`.
+	pub hallucination: f32,
+}
+
+impl Default for DigestEffect {
+	fn default() -> Self {
+		Self {
+			heal: 0.0,
+			temperature_change: 0.0,
+			fever_rate: 0.0,
+			cures_fever: false,
+			speed_multiplier: 1.0,
+			hallucination: 0.0,
+		}
+	}
+}
+
+/// The fever source added to the player by the [`DigestEffect`] of this prop
+#[derive(Component, Debug)]
+struct DigestFeverSource(Entity);
+
+/// Stacked speed multipliers stay within this range, so eating a lot of something can't freeze or launch the player
+const SPEED_MULTIPLIER_RANGE: std::ops::RangeInclusive<f32> = 0.25..=2.0;
+/// How fast hallucinations fade in and out, in strength per second
+const HALLUCINATION_FADE_SPEED: f32 = 0.5;
+
+fn start_effect(
+	eat: On<Eat>,
+	effects: Query<&DigestEffect>,
+	player: Single<Entity, With<Player>>,
+	digest_sources: Query<(Entity, &DigestFeverSource)>,
+	mut commands: Commands,
+) {
+	let Ok(effect) = effects.get(eat.body) else {
+		return;
+	};
+	let player = player.into_inner();
+
+	if effect.cures_fever {
+		for (body, source) in &digest_sources {
+			commands.entity(source.0).try_despawn();
+			commands.entity(body).remove::<DigestFeverSource>();
+		}
+	}
+	if effect.fever_rate > 0.0 {
+		let source = commands
+			.spawn((
+				Name::new("Digest Fever Source"),
+				FeverSource(effect.fever_rate),
+				FeverSourceOf(player),
+			))
+			.id();
+		commands.entity(eat.body).insert(DigestFeverSource(source));
+	}
+}
+
+fn expire_effect_on_digested(
+	digested: On<Digested>,
+	sources: Query<&DigestFeverSource>,
+	mut commands: Commands,
+) {
+	expire_effect(digested.body, &sources, &mut commands);
+}
+
+fn expire_effect_on_vomit(
+	vomit: On<Vomit>,
+	sources: Query<&DigestFeverSource>,
+	mut commands: Commands,
+) {
+	expire_effect(vomit.body, &sources, &mut commands);
+}
+
+fn expire_effect(body: Entity, sources: &Query<&DigestFeverSource>, commands: &mut Commands) {
+	let Ok(source) = sources.get(body) else {
+		return;
+	};
+	// Might already be gone if something cured the fever in the meantime
+	commands.entity(source.0).try_despawn();
+	commands.entity(body).remove::<DigestFeverSource>();
+}
+
+/// Applies the effects of everything that is being digested
+fn apply_effects(
+	stomach: Single<&Stomach>,
+	contents: Query<(&DigestEffect, &Digesting)>,
+	player: Single<(&mut Health, &mut Temperature, &mut CharacterController), With<Player>>,
+	mut settings: Query<&mut FeverPostProcessSettings>,
+	time: Res<Time>,
+) {
+	let (mut health, mut temperature, mut controller) = player.into_inner();
+	let max_health = Health::default().0;
+
+	let mut speed_multiplier = 1.0;
+	let mut hallucination = 0.0_f32;
+	for (effect, digesting) in contents.iter_many(&stomach.contents) {
+		// Spread over the digestion, so the total matches what was authored regardless of how long it takes
+		let share = time.delta_secs() / digesting.timer.duration().as_secs_f32();
+		if effect.heal != 0.0 {
+			**health = (**health + effect.heal * share).clamp(0.0, max_health.max(**health));
+		}
+		if effect.temperature_change != 0.0 {
+			**temperature += effect.temperature_change * share;
+		}
+		speed_multiplier *= effect.speed_multiplier;
+		hallucination = hallucination.max(effect.hallucination);
+	}
+
+	let speed = PLAYER_SPEED
+		* speed_multiplier.clamp(
+			*SPEED_MULTIPLIER_RANGE.start(),
+			*SPEED_MULTIPLIER_RANGE.end(),
+		);
+	if controller.speed != speed {
+		controller.speed = speed;
+	}
+
+	let max_step = HALLUCINATION_FADE_SPEED * time.delta_secs();
+	for mut setting in &mut settings {
+		let step =
+			(hallucination.clamp(0.0, 1.0) - setting.hallucination).clamp(-max_step, max_step);
+		setting.hallucination += step;
+	}
+}
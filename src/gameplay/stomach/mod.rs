@@ -23,25 +23,48 @@ use crate::{
 	gameplay::{
 		level::CurrentLevel,
 		player::{Player, camera::PlayerCameraParent},
+		stomach::{
+			eat::{EatRefusal, EatRefused},
+			reach::{StomachCamera, StomachView},
+			vomit::VomitBlocked,
+			walls::StomachMesh,
+		},
 	},
 	screens::Screen,
 	third_party::avian3d::CollisionLayer,
 	ui_layout::{RootWidgetPosition, RootWidgetPositionInterpolated},
 };
 
+pub(crate) mod digest;
 pub(crate) mod eat;
+pub(crate) mod effect;
+pub(crate) mod reach;
+pub(crate) mod snapshot;
 pub(crate) mod vomit;
+pub(crate) mod walls;
 
 pub(super) fn plugin(app: &mut App) {
 	app.load_asset::<AudioSample>("audio/music/stomach.ogg");
 	app.load_asset::<Gltf>("models/stomach/stomach.gltf");
-	app.add_plugins((eat::plugin, vomit::plugin));
+	app.add_plugins((
+		digest::plugin,
+		eat::plugin,
+		effect::plugin,
+		reach::plugin,
+		vomit::plugin,
+		walls::plugin,
+	));
 	app.add_systems(
 		OnEnter(Screen::Gameplay),
 		(spawn_stomach, spawn_stomach_ui_and_render).chain(),
 	);
 	app.add_systems(FixedUpdate, move_stomach);
-	app.add_systems(Update, update_stomach_ui_visibility);
+	app.add_systems(
+		Update,
+		(update_stomach_ui_visibility, reset_stomach_reaction_label),
+	);
+	app.add_observer(react_to_refused_eat)
+		.add_observer(react_to_blocked_vomit);
 }
 
 #[derive(Component, Reflect, Debug, Default)]
@@ -52,6 +75,8 @@ pub struct EdibleProp;
 pub struct Stomach {
 	pub target_size: Vec3,
 	pub contents: EntityHashSet,
+	/// How much fits in, in cubic meters. See [`digest::stomach_fill`].
+	pub capacity: f32,
 }
 
 impl Default for Stomach {
@@ -60,6 +85,7 @@ impl Default for Stomach {
 			// We use a fairly large z-size, but movement is still locked in the z-axis.
 			target_size: Vec3::new(2.5, 5.0, 10.0),
 			contents: EntityHashSet::new(),
+			capacity: 1.0,
 		}
 	}
 }
@@ -67,6 +93,15 @@ impl Default for Stomach {
 #[derive(Component, Debug)]
 pub struct StomachUi;
 
+/// The label next to the "LIVE" indicator.
+/// It shouts about the stomach being full for a moment when eating is refused.
+#[derive(Component, Debug, Default)]
+struct StomachReactionLabel {
+	reset_timer: Option<Timer>,
+}
+
+const STOMACH_REACTION_TEXT: &str = "STOMACH REACTION";
+
 /// The offscreen position of the stomach.
 const STOMACH_POSITION: Vec3 = Vec3::new(2000.0, 2000.0, 2000.0);
 
@@ -76,7 +111,8 @@ const STOMACH_WIDTH_VW: f32 = 15.0;
 fn spawn_stomach(mut commands: Commands, assets: Res<AssetServer>) {
 	let stomach = Stomach::default();
 
-	// TODO: Make the walls springy
+	// The sides, floor and ceiling are springy and spawned separately, see `walls`.
+	// Contents are locked on the Z axis anyway, so the front and back can stay put.
 	commands.spawn((
 		Name::new("Stomach"),
 		Stomach::default(),
@@ -85,34 +121,6 @@ fn spawn_stomach(mut commands: Commands, assets: Res<AssetServer>) {
 		DespawnOnExit(Screen::Gameplay),
 		Visibility::default(),
 		children![
-			(
-				Name::new("Stomach Left Wall"),
-				Pickable::IGNORE,
-				Collider::half_space(Vec3::X),
-				CollisionLayers::new(CollisionLayer::Stomach, CollisionLayer::Stomach),
-				Transform::from_translation(Vec3::new(-stomach.target_size.x / 2.0, 0.0, 0.0,)),
-			),
-			(
-				Name::new("Stomach Right Wall"),
-				Pickable::IGNORE,
-				Collider::half_space(-Vec3::X),
-				CollisionLayers::new(CollisionLayer::Stomach, CollisionLayer::Stomach),
-				Transform::from_translation(Vec3::new(stomach.target_size.x / 2.0, 0.0, 0.0,)),
-			),
-			(
-				Name::new("Stomach Ceiling"),
-				Pickable::IGNORE,
-				Collider::half_space(-Vec3::Y),
-				CollisionLayers::new(CollisionLayer::Stomach, CollisionLayer::Stomach),
-				Transform::from_translation(Vec3::new(0.0, stomach.target_size.y / 2.0, 0.0)),
-			),
-			(
-				Name::new("Stomach Floor"),
-				Pickable::IGNORE,
-				Collider::half_space(Vec3::Y),
-				CollisionLayers::new(CollisionLayer::Stomach, CollisionLayer::Stomach),
-				Transform::from_translation(Vec3::new(0.0, -stomach.target_size.y / 2.0, 0.0)),
-			),
 			(
 				Name::new("Stomach Back Wall"),
 				Pickable::IGNORE,
@@ -129,6 +137,7 @@ fn spawn_stomach(mut commands: Commands, assets: Res<AssetServer>) {
 			),
 			(
 				Name::new("Stomach Mesh"),
+				StomachMesh,
 				Pickable::IGNORE,
 				Propagate::<RenderLayers>(RenderLayers::from(RenderLayer::STOMACH)),
 				Transform::from_scale(stomach.target_size),
@@ -159,6 +168,7 @@ fn spawn_stomach_ui_and_render(
 	// Spawn stomach camera.
 	commands.spawn((
 		Name::new("Stomach Camera"),
+		StomachCamera,
 		ChildOf(stomach_entity),
 		Transform::from_xyz(0.0, 0.0, 20.0),
 		Camera3d::default(),
@@ -235,7 +245,8 @@ fn spawn_stomach_ui_and_render(
 					),
 					(
 						Name::new("Stomach reaction label"),
-						Text("STOMACH REACTION".into()),
+						StomachReactionLabel::default(),
+						Text(STOMACH_REACTION_TEXT.into()),
 						TextFont {
 							font: VARIABLE_FONT,
 							font_size: 18.0,
@@ -247,6 +258,8 @@ fn spawn_stomach_ui_and_render(
 				]
 			),
 			(
+				Name::new("Stomach View"),
+				StomachView,
 				Node {
 					width: Val::Vw(STOMACH_WIDTH_VW),
 					height: Val::Vw(STOMACH_WIDTH_VW / aspect_ratio),
@@ -258,6 +271,8 @@ fn spawn_stomach_ui_and_render(
 					image: image_handle,
 					..default()
 				},
+				// Lights up while reaching into the stomach
+				Outline::new(Val::Px(3.0), Val::ZERO, Color::NONE),
 			)
 		],
 	));
@@ -328,3 +343,38 @@ fn update_stomach_ui_visibility(
 		interpolated.0 = position.0;
 	}
 }
+
+fn react_to_refused_eat(
+	refused: On<EatRefused>,
+	label: Single<(&mut Text, &mut StomachReactionLabel)>,
+) {
+	if refused.reason != EatRefusal::TooFull {
+		return;
+	}
+	let (mut text, mut label) = label.into_inner();
+	text.0 = "TOO FULL!".into();
+	label.reset_timer = Some(Timer::from_seconds(2.0, TimerMode::Once));
+}
+
+fn react_to_blocked_vomit(
+	_blocked: On<VomitBlocked>,
+	label: Single<(&mut Text, &mut StomachReactionLabel)>,
+) {
+	let (mut text, mut label) = label.into_inner();
+	text.0 = "NO ROOM!".into();
+	label.reset_timer = Some(Timer::from_seconds(2.0, TimerMode::Once));
+}
+
+fn reset_stomach_reaction_label(
+	label: Single<(&mut Text, &mut StomachReactionLabel)>,
+	time: Res<Time>,
+) {
+	let (mut text, mut label) = label.into_inner();
+	let Some(timer) = &mut label.reset_timer else {
+		return;
+	};
+	if timer.tick(time.delta()).is_finished() {
+		label.reset_timer = None;
+		text.0 = STOMACH_REACTION_TEXT.into();
+	}
+}
@@ -0,0 +1,368 @@
+//! Reaching into the stomach through the stomach view.
+//!
+//! While reaching, the cursor is freed and pointer positions on the stomach view are mapped into rays from the stomach camera.
+//! Clicking a content pokes it, holding drags it around and the secondary button marks it to be vomited next.
+//! On gamepads, the sticks move the cursor.
+
+use std::any::Any as _;
+
+use avian3d::prelude::*;
+use bevy::{ecs::spawn::SpawnIter, prelude::*, window::PrimaryWindow};
+use bevy_enhanced_input::prelude::{Press, *};
+
+use crate::{
+	gameplay::{
+		crosshair::CrosshairState,
+		player::{
+			controls::{ControlAction, ControlSettings},
+			input::{BlocksInput, ReachIntoStomach},
+		},
+		stomach::{Stomach, StomachUi, vomit::VomitSelection},
+	},
+	screens::Screen,
+	third_party::avian3d::CollisionLayer,
+};
+
+pub(super) fn plugin(app: &mut App) {
+	app.add_input_context::<StomachReachInputContext>();
+	app.add_observer(start_reaching)
+		.add_observer(stop_reaching)
+		.add_observer(leave_stomach)
+		.add_observer(grab_content)
+		.add_observer(release_content)
+		.add_observer(mark_for_vomit);
+	app.add_systems(
+		Update,
+		(
+			stop_reaching_when_hidden,
+			move_cursor_with_gamepad,
+			aim_into_stomach,
+		)
+			.chain()
+			.run_if(resource_exists::<Reaching>),
+	);
+	app.add_systems(
+		FixedUpdate,
+		drag_content.run_if(resource_exists::<Reaching>),
+	);
+	app.add_systems(OnExit(Screen::Gameplay), |mut commands: Commands| {
+		commands.trigger(StopReaching);
+	});
+}
+
+/// Marker component for the image node showing what the [`StomachCamera`] renders
+#[derive(Component, Debug)]
+pub(crate) struct StomachView;
+
+/// Marker component for the orthographic camera looking into the stomach
+#[derive(Component, Debug)]
+pub(crate) struct StomachCamera;
+
+/// Lets go of the stomach with the same button used to reach into it
+#[derive(Debug, InputAction)]
+#[action_output(bool)]
+struct LeaveStomach;
+
+/// Moves the cursor over the stomach view on gamepads
+#[derive(Debug, InputAction)]
+#[action_output(Vec2)]
+struct MoveStomachCursor;
+
+/// Fires for as long as the button is held. Tapping pokes, holding drags.
+#[derive(Debug, InputAction)]
+#[action_output(bool)]
+struct GrabContent;
+
+#[derive(Debug, InputAction)]
+#[action_output(bool)]
+struct MarkForVomit;
+
+#[derive(Debug, Component, Default)]
+struct StomachReachInputContext;
+
+/// Event for letting go of the stomach and giving control back to the player
+#[derive(Event, Debug)]
+pub(crate) struct StopReaching;
+
+/// [`Resource`] that exists while the player is reaching into the stomach
+#[derive(Resource, Debug)]
+struct Reaching {
+	/// Holds the [`StomachReachInputContext`]
+	input: Entity,
+	/// Where the cursor points into the stomach, `None` while it's not over the stomach view
+	ray: Option<Ray3d>,
+	grab: Option<Grab>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Grab {
+	body: Entity,
+	/// The grabbed point, relative to the body
+	anchor: Vec3,
+}
+
+/// Rays start at the stomach camera, which is well outside the stomach
+const MAX_REACH_DISTANCE: f32 = 100.0;
+/// Holding the grab button for less than this pokes instead of dragging
+const POKE_SECONDS: f32 = 0.2;
+/// How fast a poke pushes the content away from the cursor, in meters per second
+const POKE_SPEED: f32 = 4.0;
+/// How fast a dragged content follows the cursor, per meter it's away from it
+const DRAG_STIFFNESS: f32 = 12.0;
+const MAX_DRAG_SPEED: f32 = 10.0;
+/// How fast the gamepad moves the cursor, in stomach view sizes per second
+const GAMEPAD_CURSOR_SPEED: f32 = 0.8;
+const REACHING_OUTLINE: Color = Color::srgb(0.35, 0.8, 0.2);
+
+fn start_reaching(
+	_on: On<Start<ReachIntoStomach>>,
+	reaching: Option<Res<Reaching>>,
+	stomach_ui: Single<&Node, With<StomachUi>>,
+	view: Single<(&UiGlobalTransform, &mut Outline), With<StomachView>>,
+	mut window: Single<&mut Window, With<PrimaryWindow>>,
+	mut crosshair: Single<&mut CrosshairState>,
+	mut blocks_input: ResMut<BlocksInput>,
+	settings: Res<ControlSettings>,
+	mut commands: Commands,
+) {
+	// Nothing to reach for while the stomach view is hidden
+	if reaching.is_some() || stomach_ui.display == Display::None {
+		return;
+	}
+	let leave_bindings = settings
+		.bindings_of(ControlAction::ReachIntoStomach)
+		.into_iter()
+		.chain([GamepadButton::East.into()]);
+	let input = commands
+		.spawn((
+			Name::new("Stomach Reach Input"),
+			StomachReachInputContext,
+			DespawnOnExit(Screen::Gameplay),
+			actions!(StomachReachInputContext[
+				(
+					Action::<LeaveStomach>::new(),
+					// Reaching in was pressed on the same button, so wait for it to be released first
+					ActionSettings { require_reset: true, ..default() },
+					Press::default(),
+					Bindings::spawn(SpawnIter(leave_bindings)),
+				),
+				(
+					Action::<MoveStomachCursor>::new(),
+					DeadZone::default(),
+					Bindings::spawn((Axial::left_stick(), Axial::right_stick())),
+				),
+				(
+					Action::<GrabContent>::new(),
+					bindings![MouseButton::Left, GamepadButton::South],
+				),
+				(
+					Action::<MarkForVomit>::new(),
+					Press::default(),
+					bindings![MouseButton::Right, GamepadButton::West],
+				),
+			]),
+		))
+		.id();
+	commands.insert_resource(Reaching {
+		input,
+		ray: None,
+		grab: None,
+	});
+
+	crosshair.wants_free_cursor.insert(start_reaching.type_id());
+	blocks_input.insert(start_reaching.type_id());
+
+	// Start in the middle of the view, so gamepad players don't have to look for the cursor
+	let (transform, mut outline) = view.into_inner();
+	window.set_physical_cursor_position(Some(transform.translation.as_dvec2()));
+	outline.color = REACHING_OUTLINE;
+}
+
+fn stop_reaching(
+	_on: On<StopReaching>,
+	reaching: Option<Res<Reaching>>,
+	crosshair: Option<Single<&mut CrosshairState>>,
+	view: Option<Single<&mut Outline, With<StomachView>>>,
+	mut blocks_input: ResMut<BlocksInput>,
+	mut commands: Commands,
+) {
+	let Some(reaching) = reaching else {
+		return;
+	};
+	commands.entity(reaching.input).try_despawn();
+	commands.remove_resource::<Reaching>();
+
+	if let Some(mut crosshair) = crosshair {
+		crosshair
+			.wants_free_cursor
+			.remove(&start_reaching.type_id());
+	}
+	blocks_input.remove(&start_reaching.type_id());
+	if let Some(mut outline) = view {
+		outline.color = Color::NONE;
+	}
+}
+
+fn leave_stomach(_on: On<Start<LeaveStomach>>, mut commands: Commands) {
+	commands.trigger(StopReaching);
+}
+
+/// Lets go once the stomach view disappears, e.g. because the last content was vomited
+fn stop_reaching_when_hidden(stomach_ui: Single<&Node, With<StomachUi>>, mut commands: Commands) {
+	if stomach_ui.display == Display::None {
+		commands.trigger(StopReaching);
+	}
+}
+
+/// Moves the cursor with the sticks, keeping it within the stomach view
+fn move_cursor_with_gamepad(
+	movement: Single<&Action<MoveStomachCursor>>,
+	view: Single<(&ComputedNode, &UiGlobalTransform), With<StomachView>>,
+	mut window: Single<&mut Window, With<PrimaryWindow>>,
+	time: Res<Time>,
+) {
+	let movement = ***movement;
+	if movement == Vec2::ZERO {
+		return;
+	}
+	let (node, transform) = *view;
+	let size = node.size();
+	let center = transform.translation;
+	let cursor = window.physical_cursor_position().unwrap_or(center);
+	// Pushing the stick up moves the cursor up, which is towards lower y in the UI
+	let step = Vec2::new(movement.x, -movement.y) * size * GAMEPAD_CURSOR_SPEED * time.delta_secs();
+	let cursor = (cursor + step).clamp(center - size / 2.0, center + size / 2.0);
+	window.set_physical_cursor_position(Some(cursor.as_dvec2()));
+}
+
+/// Maps the cursor through the stomach view into a ray from the stomach camera
+fn aim_into_stomach(
+	mut reaching: ResMut<Reaching>,
+	window: Single<&Window, With<PrimaryWindow>>,
+	view: Single<(&ComputedNode, &UiGlobalTransform), With<StomachView>>,
+	camera: Single<(&Camera, &GlobalTransform), With<StomachCamera>>,
+) {
+	let (node, transform) = *view;
+	let (camera, camera_transform) = *camera;
+	// UI nodes are laid out in physical pixels
+	reaching.ray = window
+		.physical_cursor_position()
+		.and_then(|cursor| node.normalize_point(*transform, cursor))
+		.filter(|point| point.abs().cmple(Vec2::splat(0.5)).all())
+		.and_then(|point| {
+			let viewport_size = camera.logical_viewport_size()?;
+			camera
+				.viewport_to_world(camera_transform, (point + 0.5) * viewport_size)
+				.ok()
+		});
+}
+
+/// The content of the stomach under the cursor, and the point on it that the cursor is over
+fn content_under_cursor(
+	ray: Ray3d,
+	stomach: &Stomach,
+	colliders: &Query<&ColliderOf>,
+	spatial: &SpatialQuery,
+) -> Option<(Entity, Vec3)> {
+	let body_of = |entity| {
+		colliders
+			.get(entity)
+			.ok()
+			.map(|collider_of| collider_of.body)
+	};
+	// The walls are on the stomach layer too, so only look for contents
+	let hit = spatial.cast_ray_predicate(
+		ray.origin,
+		ray.direction,
+		MAX_REACH_DISTANCE,
+		true,
+		&SpatialQueryFilter::from_mask(CollisionLayer::Stomach),
+		&|entity| body_of(entity).is_some_and(|body| stomach.contents.contains(&body)),
+	)?;
+	Some((body_of(hit.entity)?, ray.get_point(hit.distance)))
+}
+
+fn grab_content(
+	_on: On<Start<GrabContent>>,
+	mut reaching: ResMut<Reaching>,
+	stomach: Single<&Stomach>,
+	bodies: Query<(&Position, &Rotation)>,
+	colliders: Query<&ColliderOf>,
+	spatial: SpatialQuery,
+) {
+	let Some((body, point)) = reaching
+		.ray
+		.and_then(|ray| content_under_cursor(ray, &stomach, &colliders, &spatial))
+	else {
+		return;
+	};
+	let Ok((position, rotation)) = bodies.get(body) else {
+		return;
+	};
+	reaching.grab = Some(Grab {
+		body,
+		anchor: rotation.inverse() * (point - position.0),
+	});
+}
+
+/// Lets go of the grabbed content. A short tap pokes it away from the cursor instead.
+fn release_content(
+	release: On<Complete<GrabContent>>,
+	mut reaching: ResMut<Reaching>,
+	mut bodies: Query<(&Rotation, &mut LinearVelocity)>,
+) {
+	let Some(grab) = reaching.grab.take() else {
+		return;
+	};
+	if release.fired_secs >= POKE_SECONDS {
+		return;
+	}
+	let Ok((rotation, mut velocity)) = bodies.get_mut(grab.body) else {
+		return;
+	};
+	let direction = Dir3::new(-(rotation.0 * grab.anchor)).unwrap_or(Dir3::Y);
+	velocity.0 += POKE_SPEED * direction;
+	// Contents are locked on the z axis anyway, but keep them from fighting the lock
+	velocity.z = 0.0;
+}
+
+fn mark_for_vomit(
+	_on: On<Start<MarkForVomit>>,
+	reaching: Res<Reaching>,
+	stomach: Single<&Stomach>,
+	colliders: Query<&ColliderOf>,
+	spatial: SpatialQuery,
+	mut selection: ResMut<VomitSelection>,
+) {
+	if let Some((body, _)) = reaching
+		.ray
+		.and_then(|ray| content_under_cursor(ray, &stomach, &colliders, &spatial))
+	{
+		selection.select(body);
+	}
+}
+
+/// Pulls the grabbed point of the content towards the cursor
+fn drag_content(
+	mut reaching: ResMut<Reaching>,
+	stomach: Single<&Stomach>,
+	mut bodies: Query<(&Position, &Rotation, &mut LinearVelocity)>,
+) {
+	let (Some(grab), Some(ray)) = (reaching.grab, reaching.ray) else {
+		return;
+	};
+	// Digested or vomited while being held
+	if !stomach.contents.contains(&grab.body) {
+		reaching.grab = None;
+		return;
+	}
+	let Ok((position, rotation, mut velocity)) = bodies.get_mut(grab.body) else {
+		return;
+	};
+	let anchor = position.0 + rotation.0 * grab.anchor;
+	let Some(distance) = ray.intersect_plane(anchor, InfinitePlane3d::new(ray.direction)) else {
+		return;
+	};
+	let target = ray.get_point(distance);
+	velocity.0 = ((target - anchor) * DRAG_STIFFNESS).clamp_length_max(MAX_DRAG_SPEED);
+}
@@ -0,0 +1,302 @@
+//! Remembers what eating changes about a prop, so vomiting it brings it back exactly the way it was.
+
+use avian3d::prelude::*;
+use bevy::{camera::visibility::RenderLayers, prelude::*};
+use bevy_landmass::{Character, prelude::*};
+
+/// The physics, render and navigation state of an eaten prop from right before it was eaten
+#[derive(Component, Debug, Clone)]
+pub(crate) struct EatenSnapshot {
+	pub(crate) locked_axes: Option<LockedAxes>,
+	pub(crate) rigid_body_disabled: bool,
+	/// The body and all of its descendants
+	pub(crate) entities: Vec<EntitySnapshot>,
+}
+
+/// The state of a single entity of an eaten prop
+#[derive(Debug, Clone)]
+pub(crate) struct EntitySnapshot {
+	pub(crate) entity: Entity,
+	pub(crate) collision_layers: Option<CollisionLayers>,
+	pub(crate) render_layers: Option<RenderLayers>,
+	pub(crate) has_mesh: bool,
+	pub(crate) collider_disabled: bool,
+	pub(crate) character: Option<CharacterSnapshot>,
+}
+
+/// A landmass character, which can't be cloned as is
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CharacterSnapshot {
+	radius: f32,
+	archipelago: Entity,
+}
+
+pub(crate) type SnapshotBodyQuery<'w, 's> =
+	Query<'w, 's, (Option<&'static LockedAxes>, Has<RigidBodyDisabled>)>;
+
+pub(crate) type SnapshotEntityQuery<'w, 's> = Query<
+	'w,
+	's,
+	(
+		Option<&'static CollisionLayers>,
+		Option<&'static RenderLayers>,
+		Has<Mesh3d>,
+		Has<ColliderDisabled>,
+		Option<(&'static CharacterSettings, &'static ArchipelagoRef3d)>,
+	),
+>;
+
+impl EatenSnapshot {
+	pub(crate) fn take(
+		body: Entity,
+		bodies: &SnapshotBodyQuery,
+		entities: &SnapshotEntityQuery,
+		children: &Query<&Children>,
+	) -> Self {
+		let (locked_axes, rigid_body_disabled) = bodies.get(body).unwrap_or_default();
+		let entities = std::iter::once(body)
+			.chain(children.iter_descendants(body))
+			.filter_map(|entity| {
+				let (collision_layers, render_layers, has_mesh, collider_disabled, character) =
+					entities.get(entity).ok()?;
+				Some(EntitySnapshot {
+					entity,
+					collision_layers: collision_layers.copied(),
+					render_layers: render_layers.cloned(),
+					has_mesh,
+					collider_disabled,
+					character: character.map(|(settings, archipelago)| CharacterSnapshot {
+						radius: settings.radius,
+						archipelago: archipelago.entity,
+					}),
+				})
+			})
+			.collect();
+		Self {
+			locked_axes: locked_axes.copied(),
+			rigid_body_disabled,
+			entities,
+		}
+	}
+
+	/// Puts everything back the way it was. Entities that were despawned in the meantime are skipped.
+	pub(crate) fn restore(&self, body: Entity, commands: &mut Commands) {
+		if let Ok(mut body) = commands.get_entity(body) {
+			match self.locked_axes {
+				Some(locked_axes) => body.insert(locked_axes),
+				None => body.remove::<LockedAxes>(),
+			};
+			if self.rigid_body_disabled {
+				body.insert(RigidBodyDisabled);
+			}
+		}
+
+		for snapshot in &self.entities {
+			let Ok(mut entity) = commands.get_entity(snapshot.entity) else {
+				continue;
+			};
+			match snapshot.collision_layers {
+				Some(layers) => entity.insert(layers),
+				None => entity.remove::<CollisionLayers>(),
+			};
+			match &snapshot.render_layers {
+				Some(layers) => entity.insert(layers.clone()),
+				None => entity.remove::<RenderLayers>(),
+			};
+			if snapshot.collider_disabled {
+				entity.insert(ColliderDisabled);
+			}
+			if let Some(character) = snapshot.character {
+				entity.insert(Character3dBundle {
+					character: Character::default(),
+					settings: CharacterSettings {
+						radius: character.radius,
+					},
+					archipelago_ref: ArchipelagoRef3d::new(character.archipelago),
+				});
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		RenderLayer,
+		gameplay::stomach::{
+			Stomach,
+			eat::{Eat, on_eat},
+			vomit::{Vomit, on_vomit},
+		},
+		third_party::avian3d::CollisionLayer,
+	};
+
+	fn world_with_stomach() -> World {
+		let mut world = World::new();
+		world.add_observer(on_eat);
+		world.add_observer(on_vomit);
+		world.spawn((Stomach::default(), GlobalTransform::default()));
+		world
+	}
+
+	fn eat(world: &mut World, body: Entity) {
+		world.trigger(Eat { body });
+		world.flush();
+	}
+
+	fn vomit(world: &mut World, body: Entity) {
+		world.trigger(Vomit {
+			body,
+			origin: Vec3::ZERO,
+			linear_velocity: Vec3::ZERO,
+		});
+		world.flush();
+	}
+
+	fn prop_layers() -> CollisionLayers {
+		CollisionLayers::new(
+			CollisionLayer::Prop,
+			[CollisionLayer::Default, CollisionLayer::Prop],
+		)
+	}
+
+	#[test]
+	fn round_trip_plain_prop() {
+		let mut world = world_with_stomach();
+		let body = world
+			.spawn((
+				Transform::default(),
+				LinearVelocity::ZERO,
+				prop_layers(),
+				children![Mesh3d::default()],
+			))
+			.id();
+		let mesh = world.entity(body).get::<Children>().unwrap()[0];
+
+		eat(&mut world, body);
+		let layers = world.entity(body).get::<CollisionLayers>().unwrap();
+		assert!(layers.memberships.has_all(CollisionLayer::Stomach));
+		assert!(
+			world
+				.entity(body)
+				.get::<LockedAxes>()
+				.unwrap()
+				.is_translation_z_locked()
+		);
+		assert_eq!(
+			world.entity(mesh).get::<RenderLayers>(),
+			Some(&RenderLayers::from(RenderLayer::STOMACH))
+		);
+
+		vomit(&mut world, body);
+		assert_eq!(
+			world.entity(body).get::<CollisionLayers>(),
+			Some(&prop_layers())
+		);
+		assert!(!world.entity(body).contains::<LockedAxes>());
+		assert!(!world.entity(mesh).contains::<RenderLayers>());
+		assert!(!world.entity(body).contains::<EatenSnapshot>());
+	}
+
+	#[test]
+	fn round_trip_custom_locks_and_layers() {
+		let mut world = world_with_stomach();
+		let locked_axes = LockedAxes::new().lock_rotation_x().lock_rotation_z();
+		let render_layers = RenderLayers::layer(5);
+		let body = world
+			.spawn((
+				Transform::default(),
+				LinearVelocity::ZERO,
+				locked_axes,
+				children![(Mesh3d::default(), render_layers.clone())],
+			))
+			.id();
+		let mesh = world.entity(body).get::<Children>().unwrap()[0];
+
+		eat(&mut world, body);
+		let eaten_axes = world.entity(body).get::<LockedAxes>().unwrap();
+		assert!(eaten_axes.is_translation_z_locked());
+		assert!(eaten_axes.is_rotation_x_locked());
+		assert!(eaten_axes.is_rotation_z_locked());
+
+		vomit(&mut world, body);
+		assert_eq!(
+			world.entity(body).get::<LockedAxes>().unwrap().to_bits(),
+			locked_axes.to_bits()
+		);
+		assert_eq!(
+			world.entity(mesh).get::<RenderLayers>(),
+			Some(&render_layers)
+		);
+		// Props without layers of their own keep using the default ones
+		assert!(!world.entity(body).contains::<CollisionLayers>());
+	}
+
+	#[test]
+	fn round_trip_disabled_physics() {
+		let mut world = world_with_stomach();
+		let body = world
+			.spawn((
+				Transform::default(),
+				LinearVelocity::ZERO,
+				RigidBodyDisabled,
+				children![(prop_layers(), ColliderDisabled)],
+			))
+			.id();
+		let collider = world.entity(body).get::<Children>().unwrap()[0];
+
+		eat(&mut world, body);
+		assert!(!world.entity(body).contains::<RigidBodyDisabled>());
+		assert!(!world.entity(collider).contains::<ColliderDisabled>());
+
+		vomit(&mut world, body);
+		assert!(world.entity(body).contains::<RigidBodyDisabled>());
+		assert!(world.entity(collider).contains::<ColliderDisabled>());
+		assert_eq!(
+			world.entity(collider).get::<CollisionLayers>(),
+			Some(&prop_layers())
+		);
+	}
+
+	#[test]
+	fn round_trip_landmass_character() {
+		let mut world = world_with_stomach();
+		let archipelago = world.spawn_empty().id();
+		let body = world
+			.spawn((
+				Transform::default(),
+				LinearVelocity::ZERO,
+				children![Character3dBundle {
+					character: Character::default(),
+					settings: CharacterSettings { radius: 0.3 },
+					archipelago_ref: ArchipelagoRef3d::new(archipelago),
+				}],
+			))
+			.id();
+		let character = world.entity(body).get::<Children>().unwrap()[0];
+
+		eat(&mut world, body);
+		assert!(!world.entity(character).contains::<Character<ThreeD>>());
+		assert!(!world.entity(character).contains::<ArchipelagoRef3d>());
+
+		vomit(&mut world, body);
+		assert!(world.entity(character).contains::<Character<ThreeD>>());
+		assert_eq!(
+			world
+				.entity(character)
+				.get::<CharacterSettings>()
+				.unwrap()
+				.radius,
+			0.3
+		);
+		assert_eq!(
+			world
+				.entity(character)
+				.get::<ArchipelagoRef3d>()
+				.unwrap()
+				.entity,
+			archipelago
+		);
+	}
+}
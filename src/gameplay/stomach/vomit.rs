@@ -1,6 +1,6 @@
 use avian3d::prelude::*;
-use bevy::{camera::visibility::RenderLayers, prelude::*};
-use bevy_enhanced_input::prelude::Start;
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::{Complete, Start};
 use bevy_seedling::{
 	prelude::Volume,
 	sample::{AudioSample, RandomPitch, SamplePlayer},
@@ -10,20 +10,86 @@ use bevy_shuffle_bag::ShuffleBag;
 use crate::{
 	audio::SfxPool,
 	gameplay::{
-		player::{Player, camera::PlayerCameraParent, input::VomitObject},
-		stomach::Stomach,
+		interaction::{
+			InteractionSystems,
+			highlight::{Highlight, highlight_plugin},
+		},
+		player::{
+			Player,
+			camera::PlayerCameraParent,
+			input::{CycleVomit, VomitObject},
+		},
+		stomach::{Stomach, digest::Digesting, snapshot::EatenSnapshot},
 	},
 	third_party::avian3d::CollisionLayer,
 };
 
 pub(super) fn plugin(app: &mut App) {
+	app.init_resource::<VomitSelection>();
+	app.add_plugins(highlight_plugin::<VomitHighlight>);
 	app.add_observer(on_vomit);
 	app.add_observer(try_vomit);
+	app.add_observer(cycle_vomit_selection);
+	app.add_observer(vomit_requested);
+	app.add_systems(
+		Update,
+		(keep_vomit_selection, highlight_vomit_selection)
+			.chain()
+			.after(InteractionSystems::Highlight),
+	);
 
 	app.init_resource::<VomitSounds>()
 		.add_observer(play_vom_sound);
 }
 
+/// Event for vomiting something out of the stomach, if it has anything in it.
+#[derive(Event, Debug)]
+pub struct RequestVomit {
+	/// What to vomit, or whatever is on top of the stomach's contents if `None`.
+	pub body: Option<Entity>,
+	/// How fast it is thrown away from the player, in meters per second.
+	pub throw_speed: f32,
+}
+
+impl RequestVomit {
+	/// Vomits whatever is on top as weakly as possible
+	pub fn top() -> Self {
+		Self {
+			body: None,
+			throw_speed: MIN_THROW_SPEED,
+		}
+	}
+}
+
+/// Event triggered when there's no room in front of the player to vomit an entity, so it stays in the stomach.
+#[derive(EntityEvent, Debug)]
+pub struct VomitBlocked {
+	/// The rigid body entity that couldn't be vomited.
+	#[event_target]
+	pub body: Entity,
+}
+
+/// [`Resource`] for the content of the stomach that the player vomits next.
+/// Falls back to whatever is on top when the selection is gone.
+#[derive(Resource, Debug, Default, Deref)]
+pub(crate) struct VomitSelection(Option<Entity>);
+
+impl VomitSelection {
+	pub(crate) fn select(&mut self, body: Entity) {
+		self.0 = Some(body);
+	}
+}
+
+/// Throw speed when tapping the vomit button, in meters per second
+const MIN_THROW_SPEED: f32 = 3.0;
+/// Throw speed when holding the vomit button for [`FULL_THROW_SECONDS`], in meters per second
+const MAX_THROW_SPEED: f32 = 15.0;
+const FULL_THROW_SECONDS: f32 = 1.0;
+/// How far in front of the camera vomit ends up if nothing is in the way
+const VOMIT_DISTANCE: f32 = 1.25;
+/// How many spots between the end of the vomit cast and the camera are tried when the prop doesn't fit
+const VOMIT_BACK_OFF_STEPS: u32 = 4;
+
 /// Event for vomiting an entity out of the stomach.
 #[derive(EntityEvent, Debug)]
 pub struct Vomit {
@@ -36,12 +102,11 @@ pub struct Vomit {
 	pub linear_velocity: Vec3,
 }
 
-fn on_vomit(
+pub(super) fn on_vomit(
 	vomit: On<Vomit>,
 	mut object_query: Query<(&mut Transform, &mut LinearVelocity)>,
-	mut layer_query: Query<(Option<&CollisionLayers>, Has<Mesh3d>)>,
+	snapshot_query: Query<&EatenSnapshot>,
 	mut stomach: Single<&mut Stomach>,
-	child_query: Query<&Children>,
 	mut commands: Commands,
 ) {
 	let Ok((mut transform, mut linear_velocity)) = object_query.get_mut(vomit.body) else {
@@ -52,60 +117,193 @@ fn on_vomit(
 	transform.translation = vomit.origin;
 	linear_velocity.0 = vomit.linear_velocity;
 	stomach.contents.remove(&vomit.body);
+	// Right away, as it's usually targeted in the world as soon as it lands there
+	commands.entity(vomit.body).try_remove::<VomitHighlight>();
 
-	// Unlock the entity's Z translation.
-	// TODO: Don't overwrite any other locked axes.
-	commands.entity(vomit.body).remove::<LockedAxes>();
-
-	// Remove the stomach collision and render layers.
-	for entity in std::iter::once(vomit.body).chain(child_query.iter_descendants(vomit.body)) {
-		let Ok((collision_layers, has_mesh)) = layer_query.get_mut(entity) else {
-			continue;
-		};
-
-		if let Some(collision_layers) = collision_layers {
-			let mut new_layers = *collision_layers;
-			new_layers.memberships.remove(CollisionLayer::Stomach);
-			new_layers.filters.remove(CollisionLayer::Stomach);
-			commands.entity(entity).insert(new_layers);
-		}
-
-		if has_mesh {
-			commands.entity(entity).remove::<RenderLayers>();
-		}
+	// Restore the locked axes, layers and everything else eating changed.
+	if let Ok(snapshot) = snapshot_query.get(vomit.body) {
+		snapshot.restore(vomit.body, &mut commands);
+		commands.entity(vomit.body).remove::<EatenSnapshot>();
 	}
 }
 
+/// Vomits the selection once the button is released, thrown further the longer it was held
 fn try_vomit(
-	_vomit: On<Start<VomitObject>>,
+	vomit: On<Complete<VomitObject>>,
+	selection: Res<VomitSelection>,
+	mut commands: Commands,
+) {
+	let charge = (vomit.fired_secs / FULL_THROW_SECONDS).clamp(0.0, 1.0);
+	commands.trigger(RequestVomit {
+		body: selection.0,
+		throw_speed: MIN_THROW_SPEED.lerp(MAX_THROW_SPEED, charge),
+	});
+}
+
+/// Selects the next content, in the order they were eaten in
+fn cycle_vomit_selection(
+	_cycle: On<Start<CycleVomit>>,
+	stomach: Single<&Stomach>,
+	mut selection: ResMut<VomitSelection>,
+) {
+	let mut contents: Vec<Entity> = stomach.contents.iter().copied().collect();
+	// Entity order is the best we have for a stable order, as the contents slosh around
+	contents.sort();
+	let next = selection
+		.0
+		.and_then(|selected| contents.iter().position(|&body| body == selected))
+		.map_or(0, |index| (index + 1) % contents.len().max(1));
+	selection.0 = contents.get(next).copied();
+}
+
+/// Selects whatever is on top when the selection was vomited or digested
+fn keep_vomit_selection(
+	stomach: Single<&Stomach>,
+	vomitables: Query<(Entity, &GlobalTransform), With<RigidBody>>,
+	mut selection: ResMut<VomitSelection>,
+) {
+	if selection
+		.0
+		.is_some_and(|selected| stomach.contents.contains(&selected))
+	{
+		return;
+	}
+	let top = top_content(&stomach, &vomitables);
+	if selection.0 != top {
+		selection.0 = top;
+	}
+}
+
+/// The content of the stomach with the highest position
+fn top_content(
+	stomach: &Stomach,
+	vomitables: &Query<(Entity, &GlobalTransform), With<RigidBody>>,
+) -> Option<Entity> {
+	vomitables
+		.iter_many(stomach.contents.iter())
+		.max_by(|a, b| a.1.translation().y.total_cmp(&b.1.translation().y))
+		.map(|(body, _)| body)
+}
+
+/// The content of the stomach that is selected for vomiting
+#[derive(Component, Debug)]
+struct VomitHighlight;
+
+impl Highlight for VomitHighlight {
+	// A sickly green
+	const COLOR: LinearRgba = LinearRgba::new(0.1, 0.3, 0.05, 1.0);
+}
+
+/// Highlights the selection in the stomach view
+fn highlight_vomit_selection(
+	selection: Res<VomitSelection>,
+	mut highlighted: Local<Option<Entity>>,
+	mut commands: Commands,
+) {
+	if *highlighted == selection.0 {
+		return;
+	}
+	if let Some(previous) = *highlighted {
+		commands.entity(previous).try_remove::<VomitHighlight>();
+	}
+	if let Some(selected) = selection.0 {
+		commands.entity(selected).try_insert(VomitHighlight);
+	}
+	*highlighted = selection.0;
+}
+
+/// Vomits the requested content to the closest spot in front of the player that it fits into
+fn vomit_requested(
+	request: On<RequestVomit>,
 	player_camera_transform: Single<&GlobalTransform, With<PlayerCameraParent>>,
-	player_velocity: Single<&LinearVelocity, With<Player>>,
+	player: Single<(Entity, &LinearVelocity), With<Player>>,
 	stomach: Single<&Stomach>,
 	vomitables: Query<(Entity, &GlobalTransform), With<RigidBody>>,
+	children: Query<&Children>,
+	colliders: Query<(&Collider, &ColliderOf, &ColliderAabb), Without<Sensor>>,
+	digesting: Query<&Digesting>,
+	sensors: Query<Entity, With<Sensor>>,
+	spatial: SpatialQuery,
 	mut commands: Commands,
 ) {
-	let forward = player_camera_transform.forward();
-	// TODO: Figure out a safe distance from the player to vomit from.
-	let origin = player_camera_transform.translation() + 1.25 * forward;
-	let linear_velocity = player_velocity.0 + 10.0 * forward;
+	let body = request
+		.body
+		.filter(|body| stomach.contents.contains(body))
+		.or_else(|| top_content(&stomach, &vomitables));
+	let Some(body) = body else {
+		return;
+	};
+	let Ok((_, body_transform)) = vomitables.get(body) else {
+		return;
+	};
+	let (player, player_velocity) = player.into_inner();
 
-	// Find the highest entity in the stomach to vomit out.
-	let vomit_candidate = vomitables
-		.iter_many(stomach.contents.iter())
-		.max_by(|a, b| {
-			a.1.translation()
-				.y
-				.partial_cmp(&b.1.translation().y)
-				.unwrap()
+	// Cast the largest solid collider of the prop, at the size it will have once it's out
+	let shape = colliders
+		.iter_many(std::iter::once(body).chain(children.iter_descendants(body)))
+		.filter(|(_, collider_of, _)| collider_of.body == body)
+		.max_by(|(_, _, a), (_, _, b)| {
+			let volume = |aabb: &ColliderAabb| aabb.size().x * aabb.size().y * aabb.size().z;
+			volume(a).total_cmp(&volume(b))
+		})
+		.map(|(collider, _, _)| {
+			let mut collider = collider.clone();
+			if let Ok(digesting) = digesting.get(body) {
+				let scale = collider.scale() / digesting.scale_factor();
+				collider.set_scale(scale, 8);
+			}
+			collider
 		});
 
-	if let Some((body, _)) = vomit_candidate {
-		commands.trigger(Vomit {
-			body,
-			origin,
-			linear_velocity,
-		});
-	}
+	let forward = player_camera_transform.forward();
+	let eye = player_camera_transform.translation();
+	let rotation = body_transform.rotation();
+	let mask = [
+		CollisionLayer::Default,
+		CollisionLayer::Prop,
+		CollisionLayer::Character,
+	];
+	// Sensors like the player's temperature sensor don't block anything
+	let blocking =
+		SpatialQueryFilter::from_excluded_entities(sensors.iter().chain([body])).with_mask(mask);
+	let filter = SpatialQueryFilter::from_excluded_entities(sensors.iter().chain([body, player]))
+		.with_mask(mask);
+	let distance = match shape {
+		Some(shape) => {
+			let distance = spatial
+				.cast_shape(
+					&shape,
+					eye,
+					rotation,
+					forward,
+					&ShapeCastConfig::from_max_distance(VOMIT_DISTANCE),
+					&filter,
+				)
+				.map_or(VOMIT_DISTANCE, |hit| hit.distance);
+			// The cast ignores the player and can end with the prop touching a wall,
+			// so back off towards the camera until it fits and keep it down if it never does
+			let fits = |distance: f32| {
+				spatial
+					.shape_intersections(&shape, eye + distance * forward, rotation, &blocking)
+					.is_empty()
+			};
+			let Some(distance) = (0..=VOMIT_BACK_OFF_STEPS)
+				.map(|step| distance * (1.0 - step as f32 / VOMIT_BACK_OFF_STEPS as f32))
+				.find(|&distance| fits(distance))
+			else {
+				commands.trigger(VomitBlocked { body });
+				return;
+			};
+			distance
+		}
+		None => VOMIT_DISTANCE,
+	};
+
+	commands.trigger(Vomit {
+		body,
+		origin: eye + distance * forward,
+		linear_velocity: player_velocity.0 + request.throw_speed * forward,
+	});
 }
 
 #[derive(Resource)]
@@ -0,0 +1,287 @@
+//! Springy stomach walls.
+//!
+//! The sides, floor and ceiling are kinematic slabs held in place by damped springs.
+//! They are kicked around by the player jumping, landing and taking damage,
+//! and every now and then the sides squeeze in a wave from the bottom up, so the contents slosh around.
+
+use avian3d::prelude::*;
+use bevy::{picking::Pickable, prelude::*};
+use bevy_ahoy::prelude::*;
+
+use crate::{
+	gameplay::{
+		core::Health,
+		player::Player,
+		stomach::{STOMACH_POSITION, Stomach},
+	},
+	screens::Screen,
+	third_party::avian3d::CollisionLayer,
+};
+
+pub(super) fn plugin(app: &mut App) {
+	app.init_resource::<Peristalsis>();
+	app.add_observer(spawn_walls);
+	app.add_systems(
+		Update,
+		(
+			kick_walls_on_movement,
+			kick_walls_on_damage,
+			squish_stomach_mesh,
+		),
+	);
+	app.add_systems(FixedUpdate, spring_walls);
+}
+
+/// A wall slab of the stomach that springs back to its rest position
+#[derive(Component, Debug)]
+pub(crate) struct StomachWall {
+	/// Where the middle of the inner face rests, relative to the stomach
+	rest: Vec3,
+	/// Points into the stomach
+	inward: Dir3,
+	/// How far the wall is pushed into the stomach from its rest position, in meters. Negative when bulging out.
+	offset: f32,
+	/// How fast [`Self::offset`] changes, in meters per second
+	velocity: f32,
+	/// When this wall squeezes during a peristaltic wave, from `0` for the start of the wave to `1` for the end.
+	/// `None` for walls that don't take part.
+	wave_phase: Option<f32>,
+}
+
+/// Marker component for the visual stomach model, which is squished along with the walls
+#[derive(Component, Debug)]
+pub(crate) struct StomachMesh;
+
+/// Times the periodic squeezes of the stomach
+#[derive(Resource, Debug)]
+struct Peristalsis(Timer);
+
+impl Default for Peristalsis {
+	fn default() -> Self {
+		Self(Timer::from_seconds(
+			PERISTALSIS_INTERVAL_SECONDS,
+			TimerMode::Repeating,
+		))
+	}
+}
+
+const WALL_THICKNESS: f32 = 1.0;
+/// How many segments each side wall is split into, from the bottom up
+const SIDE_WALL_SEGMENTS: usize = 4;
+/// How far a wall can be pushed in or out of its rest position
+const MAX_WALL_OFFSET: f32 = 0.8;
+/// How hard the walls pull back to where they want to be, per meter of offset
+const WALL_STIFFNESS: f32 = 120.0;
+/// How quickly the springing dies down
+const WALL_DAMPING: f32 = 9.0;
+
+const PERISTALSIS_INTERVAL_SECONDS: f32 = 9.0;
+/// How long a squeeze takes to travel from the bottom to the top
+const PERISTALSIS_WAVE_SECONDS: f32 = 2.5;
+/// How long a single segment stays squeezed during a wave
+const PERISTALSIS_SQUEEZE_SECONDS: f32 = 1.0;
+/// How far the sides are pushed in at the peak of a squeeze
+const PERISTALSIS_DEPTH: f32 = 0.5;
+
+/// Kick given to the floor and ceiling when the player jumps or lands, in meters per second
+const JUMP_KICK: f32 = 3.0;
+const LAND_KICK: f32 = 4.0;
+/// Kick given to all walls per point of damage the player takes, in meters per second
+const DAMAGE_KICK_PER_HEALTH: f32 = 0.3;
+const MAX_DAMAGE_KICK: f32 = 4.0;
+
+fn spawn_walls(add: On<Add, Stomach>, stomach: Query<&Stomach>, mut commands: Commands) {
+	let Ok(stomach) = stomach.get(add.entity) else {
+		return;
+	};
+	let size = stomach.target_size;
+	// Reach past the corners, so nothing slips out between walls that moved apart
+	let overlap = 2.0 * (MAX_WALL_OFFSET + WALL_THICKNESS);
+
+	let mut walls = vec![
+		(
+			"Stomach Floor",
+			Vec3::new(0.0, -size.y / 2.0, 0.0),
+			Dir3::Y,
+			Vec2::new(size.x + overlap, size.z),
+			None,
+		),
+		(
+			"Stomach Ceiling",
+			Vec3::new(0.0, size.y / 2.0, 0.0),
+			Dir3::NEG_Y,
+			Vec2::new(size.x + overlap, size.z),
+			None,
+		),
+	];
+	let segment_height = size.y / SIDE_WALL_SEGMENTS as f32;
+	for i in 0..SIDE_WALL_SEGMENTS {
+		let y = -size.y / 2.0 + (i as f32 + 0.5) * segment_height;
+		// The bottom and top segments reach past the floor and ceiling
+		let height = if i == 0 || i == SIDE_WALL_SEGMENTS - 1 {
+			segment_height + overlap
+		} else {
+			segment_height
+		};
+		let y = match i {
+			0 => y - overlap / 2.0,
+			_ if i == SIDE_WALL_SEGMENTS - 1 => y + overlap / 2.0,
+			_ => y,
+		};
+		let phase = i as f32 / (SIDE_WALL_SEGMENTS - 1) as f32;
+		walls.push((
+			"Stomach Left Wall Segment",
+			Vec3::new(-size.x / 2.0, y, 0.0),
+			Dir3::X,
+			Vec2::new(height, size.z),
+			Some(phase),
+		));
+		walls.push((
+			"Stomach Right Wall Segment",
+			Vec3::new(size.x / 2.0, y, 0.0),
+			Dir3::NEG_X,
+			Vec2::new(height, size.z),
+			Some(phase),
+		));
+	}
+
+	for (name, rest, inward, extents, wave_phase) in walls {
+		// The slab is `WALL_THICKNESS` along `inward` and spans `extents` on the other two axes,
+		// the first one being the other axis in the XY plane.
+		let collider_size = if inward.x != 0.0 {
+			Vec3::new(WALL_THICKNESS, extents.x, extents.y)
+		} else {
+			Vec3::new(extents.x, WALL_THICKNESS, extents.y)
+		};
+		commands.spawn((
+			Name::new(name),
+			StomachWall {
+				rest,
+				inward,
+				offset: 0.0,
+				velocity: 0.0,
+				wave_phase,
+			},
+			RigidBody::Kinematic,
+			Pickable::IGNORE,
+			Collider::cuboid(collider_size.x, collider_size.y, collider_size.z),
+			CollisionLayers::new(CollisionLayer::Stomach, CollisionLayer::Stomach),
+			Transform::from_translation(STOMACH_POSITION + rest - inward * (WALL_THICKNESS / 2.0)),
+			DespawnOnExit(Screen::Gameplay),
+		));
+	}
+}
+
+/// How far a wall taking part in peristalsis wants to be pushed in at `elapsed` seconds into the cycle
+fn squeeze(wave_phase: f32, elapsed: f32) -> f32 {
+	let start = wave_phase * (PERISTALSIS_WAVE_SECONDS - PERISTALSIS_SQUEEZE_SECONDS);
+	let t = (elapsed - start) / PERISTALSIS_SQUEEZE_SECONDS;
+	if (0.0..=1.0).contains(&t) {
+		PERISTALSIS_DEPTH * (t * std::f32::consts::PI).sin()
+	} else {
+		0.0
+	}
+}
+
+/// Simulates the wall springs and moves the kinematic wall bodies along with the stomach
+fn spring_walls(
+	stomach: Single<&Position, With<Stomach>>,
+	mut walls: Query<(&mut StomachWall, &Position, &mut LinearVelocity)>,
+	mut peristalsis: ResMut<Peristalsis>,
+	time: Res<Time>,
+) {
+	let dt = time.delta_secs();
+	if dt <= 0.0 {
+		return;
+	}
+	let elapsed = peristalsis.0.tick(time.delta()).elapsed_secs();
+
+	for (mut wall, position, mut velocity) in &mut walls {
+		let target = wall.wave_phase.map_or(0.0, |phase| squeeze(phase, elapsed));
+		let acceleration = -WALL_STIFFNESS * (wall.offset - target) - WALL_DAMPING * wall.velocity;
+		wall.velocity += acceleration * dt;
+		wall.offset += wall.velocity * dt;
+		if wall.offset.abs() > MAX_WALL_OFFSET {
+			wall.offset = wall.offset.clamp(-MAX_WALL_OFFSET, MAX_WALL_OFFSET);
+			wall.velocity = 0.0;
+		}
+
+		// Kinematic bodies push the contents around properly only when moved by velocity
+		let inner_face = stomach.0 + wall.rest + wall.inward * wall.offset;
+		let center = inner_face - wall.inward * (WALL_THICKNESS / 2.0);
+		velocity.0 = (center - position.0) / dt;
+	}
+}
+
+/// Jumping lifts the contents off the floor, landing slams them into it
+fn kick_walls_on_movement(
+	player: Single<&CharacterControllerState, With<Player>>,
+	mut walls: Query<&mut StomachWall>,
+	mut was_airborne: Local<bool>,
+) {
+	let is_airborne = player.grounded.is_none();
+	if is_airborne == *was_airborne {
+		return;
+	}
+	*was_airborne = is_airborne;
+
+	let kick = if is_airborne { -JUMP_KICK } else { LAND_KICK };
+	for mut wall in &mut walls {
+		if wall.inward == Dir3::Y {
+			wall.velocity += kick;
+		} else if wall.inward == Dir3::NEG_Y {
+			wall.velocity -= kick;
+		}
+	}
+}
+
+/// The stomach cramps up when the player gets hurt
+fn kick_walls_on_damage(
+	health: Query<&Health, (With<Player>, Changed<Health>)>,
+	mut walls: Query<&mut StomachWall>,
+	mut previous_health: Local<Option<f32>>,
+) {
+	let Ok(health) = health.single() else {
+		return;
+	};
+	let health = health.0;
+	let damage = previous_health.map_or(0.0, |previous| previous - health);
+	*previous_health = Some(health);
+	if damage <= 0.0 {
+		return;
+	}
+
+	let kick = (damage * DAMAGE_KICK_PER_HEALTH).min(MAX_DAMAGE_KICK);
+	for mut wall in &mut walls {
+		wall.velocity += kick;
+	}
+}
+
+/// Squishes the stomach model so it follows the walls
+fn squish_stomach_mesh(
+	stomach: Single<&Stomach>,
+	walls: Query<&StomachWall>,
+	mut mesh: Single<&mut Transform, With<StomachMesh>>,
+) {
+	// The average offset of the walls on each side: left, right, bottom and top
+	let mut offsets = [(0.0, 0.0); 4];
+	for wall in &walls {
+		let side = if wall.inward == Dir3::X {
+			0
+		} else if wall.inward == Dir3::NEG_X {
+			1
+		} else if wall.inward == Dir3::Y {
+			2
+		} else {
+			3
+		};
+		offsets[side].0 += wall.offset;
+		offsets[side].1 += 1.0;
+	}
+	let [left, right, bottom, top] =
+		offsets.map(|(sum, count)| if count > 0.0 { sum / count } else { 0.0 });
+
+	let size = stomach.target_size;
+	mesh.scale = Vec3::new(size.x - left - right, size.y - bottom - top, size.z);
+	mesh.translation = Vec3::new((left - right) / 2.0, (bottom - top) / 2.0, 0.0);
+}
@@ -0,0 +1,140 @@
+//! Yarn variables that outlive a single [`DialogueRunner`], so story flags carry over between levels.
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use bevy_trenchbroom::prelude::*;
+use bevy_yarnspinner::{default_impl::MemoryVariableStorage, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+	app.init_resource::<YarnVariables>()
+		.init_resource::<SharedYarnStorage>();
+	app.add_systems(Update, sync_yarn_variables.before(YarnSpinnerSystemSet));
+	app.add_systems(OnEnter(Screen::Title), reset_yarn_variables);
+}
+
+/// Every Yarn variable set during this playthrough, keyed by its name including the `$`.
+///
+/// Mirrors [`SharedYarnStorage`]. Values changed here, e.g. from the inspector or by loading a save,
+/// are written back to the storage before the dialogue runners update.
+#[derive(Resource, Reflect, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub(crate) struct YarnVariables(pub(crate) BTreeMap<String, YarnVariable>);
+
+/// A Yarn value that can be reflected and serialized
+#[derive(Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum YarnVariable {
+	Bool(bool),
+	Number(f32),
+	String(String),
+}
+
+impl From<YarnValue> for YarnVariable {
+	fn from(value: YarnValue) -> Self {
+		match value {
+			YarnValue::Boolean(value) => Self::Bool(value),
+			YarnValue::Number(value) => Self::Number(value),
+			YarnValue::String(value) => Self::String(value),
+		}
+	}
+}
+
+impl From<YarnVariable> for YarnValue {
+	fn from(value: YarnVariable) -> Self {
+		match value {
+			YarnVariable::Bool(value) => Self::Boolean(value),
+			YarnVariable::Number(value) => Self::Number(value),
+			YarnVariable::String(value) => Self::String(value),
+		}
+	}
+}
+
+/// How a value written in TrenchBroom is turned into a [`YarnVariable`]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect, FgdType)]
+pub(crate) enum YarnValueType {
+	/// `true` and `false` are booleans, anything that parses as a number is a number, everything else is a string
+	#[default]
+	Auto,
+	Bool,
+	Number,
+	String,
+}
+
+impl YarnValueType {
+	pub(crate) fn parse(self, value: &str) -> Result<YarnVariable> {
+		Ok(match self {
+			Self::Auto => {
+				if let Ok(value) = value.parse() {
+					YarnVariable::Bool(value)
+				} else if let Ok(value) = value.parse() {
+					YarnVariable::Number(value)
+				} else {
+					YarnVariable::String(value.to_string())
+				}
+			}
+			Self::Bool => YarnVariable::Bool(value.parse()?),
+			Self::Number => YarnVariable::Number(value.parse()?),
+			Self::String => YarnVariable::String(value.to_string()),
+		})
+	}
+}
+
+/// The variable storage every [`DialogueRunner`] is built with.
+/// Its clones are shallow, so all runners read and write the same variables.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct SharedYarnStorage(MemoryVariableStorage);
+
+impl SharedYarnStorage {
+	/// Storage to pass to [`DialogueRunnerBuilder::with_variable_storage`]
+	pub(crate) fn storage(&self) -> Box<dyn VariableStorage> {
+		self.0.clone_shallow()
+	}
+}
+
+/// Writes the variables that were edited in [`YarnVariables`] to the storage, then mirrors the storage back
+fn sync_yarn_variables(
+	mut variables: ResMut<YarnVariables>,
+	mut storage: ResMut<SharedYarnStorage>,
+	mut synced: Local<YarnVariables>,
+) {
+	let edited = variables.is_changed();
+	if edited {
+		// Only what changed since the last sync, so the runners' own writes aren't undone by a stale mirror
+		for (name, value) in &variables.0 {
+			if synced.0.get(name) == Some(value) {
+				continue;
+			}
+			if let Err(err) = storage.0.set(name.clone(), value.clone().into()) {
+				warn!("Failed to set Yarn variable {name}: {err}");
+			}
+		}
+	}
+
+	let stored = storage.0.variables();
+	let mirrored = stored.len() == variables.0.len()
+		&& stored
+			.iter()
+			.all(|(name, value)| variables.0.get(name) == Some(&YarnVariable::from(value.clone())));
+	if !mirrored {
+		// Mirroring isn't an edit
+		variables.bypass_change_detection().0 = stored
+			.into_iter()
+			.map(|(name, value)| (name, YarnVariable::from(value)))
+			.collect();
+	}
+	if edited || !mirrored {
+		synced.0.clone_from(&variables.0);
+	}
+}
+
+/// Starts every playthrough with a clean slate
+fn reset_yarn_variables(
+	mut variables: ResMut<YarnVariables>,
+	mut storage: ResMut<SharedYarnStorage>,
+) {
+	storage.0.clear();
+	variables.0.clear();
+}
@@ -0,0 +1,316 @@
+//! The controls page of the settings menu, where every action of the player can be rebound.
+//!
+//! Clicking a binding waits for the next key, mouse button or gamepad button.
+//! Escape cancels waiting, Backspace or Delete clears the binding.
+
+use bevy::{ecs::spawn::SpawnWith, prelude::*, ui::Val::*};
+
+use crate::{
+	gameplay::player::controls::{
+		ControlAction, ControlDevice, ControlInput, ControlSettings, save_control_settings,
+	},
+	menus::Menu,
+	screens::Screen,
+	theme::{input_glyph::binding_glyph, prelude::*},
+	ui_layout::RootWidget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+	app.add_systems(OnEnter(Menu::Controls), spawn_controls_menu);
+	app.add_systems(
+		OnExit(Menu::Controls),
+		(save_control_settings, cancel_rebind),
+	);
+	app.add_systems(
+		Update,
+		(
+			// Escape cancels the rebind instead of leaving the menu, so this has to see the rebind before it's finished
+			go_back
+				.run_if(action_just_started::<MenuBack>.and(not(resource_exists::<PendingRebind>))),
+			capture_rebind.run_if(resource_exists::<PendingRebind>),
+			update_binding_labels,
+			update_invert_y_label,
+			update_gamepad_look_sensitivity_label,
+		)
+			.chain()
+			.run_if(in_state(Menu::Controls)),
+	);
+}
+
+/// The binding that waits for the player to press something
+#[derive(Resource, Debug, Clone, Copy)]
+pub(crate) struct PendingRebind {
+	action: ControlAction,
+	device: ControlDevice,
+}
+
+/// Marker component for the button showing what `action` is bound to on `device`
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct BindingButton {
+	action: ControlAction,
+	device: ControlDevice,
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct InvertYLabel;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct GamepadLookSensitivityLabel;
+
+/// Marker component for the label that tells which action lost its input to a rebind
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ConflictLabel;
+
+fn spawn_controls_menu(mut commands: Commands) {
+	commands.spawn((
+		RootWidget,
+		DespawnOnExit(Menu::Controls),
+		DespawnOnExit(Screen::Gameplay),
+		GlobalZIndex(2),
+		children![
+			widget::header("Controls"),
+			(
+				Name::new("Bindings Grid"),
+				Node {
+					display: Display::Grid,
+					row_gap: Px(4.0),
+					column_gap: Px(20.0),
+					align_items: AlignItems::Center,
+					justify_items: JustifyItems::Center,
+					grid_template_columns: vec![
+						GridTrack::px(240.0),
+						GridTrack::px(200.0),
+						GridTrack::px(200.0),
+					],
+					..default()
+				},
+				Children::spawn(SpawnWith(|parent: &mut ChildSpawner| {
+					for action in ControlAction::ALL {
+						parent.spawn(widget::label(action.name()));
+						parent.spawn(binding_button(action, ControlDevice::KeyboardMouse));
+						if action.is_movement() {
+							// Movement is always on the left stick
+							parent.spawn(widget::label("Left Stick"));
+						} else {
+							parent.spawn(binding_button(action, ControlDevice::Gamepad));
+						}
+					}
+					parent.spawn(widget::label("Look"));
+					parent.spawn(widget::label("Mouse"));
+					parent.spawn(widget::label("Right Stick"));
+				})),
+			),
+			(widget::label(""), ConflictLabel),
+			(
+				Name::new("Look Settings Grid"),
+				Node {
+					display: Display::Grid,
+					row_gap: Px(10.0),
+					column_gap: Px(30.0),
+					grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+					..default()
+				},
+				children![
+					(
+						widget::label("Invert Y"),
+						Node {
+							justify_self: JustifySelf::End,
+							..default()
+						}
+					),
+					widget::plus_minus_bar(InvertYLabel, disable_invert_y, enable_invert_y),
+					(
+						widget::label("Gamepad Look Sensitivity"),
+						Node {
+							justify_self: JustifySelf::End,
+							..default()
+						}
+					),
+					widget::plus_minus_bar(
+						GamepadLookSensitivityLabel,
+						lower_gamepad_look_sensitivity,
+						raise_gamepad_look_sensitivity
+					),
+				],
+			),
+			widget::button("Reset to defaults", reset_to_defaults),
+			widget::button("Back", go_back_on_click),
+		],
+	));
+}
+
+fn binding_button(action: ControlAction, device: ControlDevice) -> impl Bundle {
+	(
+		widget::button_medium(
+			"",
+			move |_on: On<Pointer<Click>>, mut commands: Commands| {
+				commands.insert_resource(PendingRebind { action, device });
+			},
+		),
+		BindingButton { action, device },
+	)
+}
+
+/// Binds the next key or button that is pressed to the pending action
+pub(crate) fn capture_rebind(
+	pending: Res<PendingRebind>,
+	keys: Res<ButtonInput<KeyCode>>,
+	mouse_buttons: Res<ButtonInput<MouseButton>>,
+	gamepads: Query<&Gamepad>,
+	mut settings: ResMut<ControlSettings>,
+	mut conflict_label: Single<&mut Text, With<ConflictLabel>>,
+	mut commands: Commands,
+) {
+	// The input that started the rebind must not end it right away
+	if pending.is_added() {
+		return;
+	}
+	if keys.just_pressed(KeyCode::Escape) {
+		commands.remove_resource::<PendingRebind>();
+		return;
+	}
+	if keys.any_just_pressed([KeyCode::Backspace, KeyCode::Delete]) {
+		settings.unbind(pending.action, pending.device);
+		conflict_label.0.clear();
+		commands.remove_resource::<PendingRebind>();
+		return;
+	}
+	let input = match pending.device {
+		ControlDevice::KeyboardMouse => keys
+			.get_just_pressed()
+			.next()
+			.copied()
+			.map(ControlInput::Key)
+			.or_else(|| {
+				mouse_buttons
+					.get_just_pressed()
+					.next()
+					.copied()
+					.map(ControlInput::Mouse)
+			}),
+		ControlDevice::Gamepad => gamepads
+			.iter()
+			.find_map(|gamepad| gamepad.get_just_pressed().next().copied())
+			.map(ControlInput::Gamepad),
+	};
+	let Some(input) = input else {
+		return;
+	};
+
+	let conflicts = settings.conflicts(pending.action, input);
+	conflict_label.0 = if conflicts.is_empty() {
+		String::new()
+	} else {
+		let previous = settings
+			.binding(pending.action)
+			.get(pending.device)
+			.map_or("nothing".to_string(), |previous| {
+				binding_glyph(previous.into())
+			});
+		let names: Vec<&str> = conflicts.iter().map(|conflict| conflict.name()).collect();
+		let (was, uses) = if conflicts.len() == 1 {
+			("was", "it now uses")
+		} else {
+			("were", "they now use")
+		};
+		format!(
+			"{} {was} bound to {}, {uses} {previous}",
+			names.join(", "),
+			binding_glyph(input.into()),
+		)
+	};
+	settings.rebind(pending.action, input);
+	commands.remove_resource::<PendingRebind>();
+}
+
+fn cancel_rebind(mut commands: Commands) {
+	commands.remove_resource::<PendingRebind>();
+}
+
+fn update_binding_labels(
+	buttons: Query<(Entity, &BindingButton)>,
+	children: Query<&Children>,
+	mut texts: Query<(&mut Text, &mut TextColor)>,
+	settings: Res<ControlSettings>,
+	pending: Option<Res<PendingRebind>>,
+) {
+	for (entity, button) in &buttons {
+		let waiting = pending.as_ref().is_some_and(|pending| {
+			pending.action == button.action && pending.device == button.device
+		});
+		let input = settings.binding(button.action).get(button.device);
+		let (label, color) = match input {
+			_ if waiting => ("Press...".to_string(), ui_palette::HEADER_TEXT),
+			Some(input) => (binding_glyph(input.into()), ui_palette::BUTTON_TEXT),
+			None => ("-".to_string(), ui_palette::LABEL_TEXT),
+		};
+		let mut descendant_texts = texts.iter_many_mut(children.iter_descendants(entity));
+		while let Some((mut text, mut text_color)) = descendant_texts.fetch_next() {
+			if text.0 != label {
+				text.0.clone_from(&label);
+			}
+			text_color.set_if_neq(TextColor(color));
+		}
+	}
+}
+
+fn enable_invert_y(_on: On<Pointer<Click>>, mut settings: ResMut<ControlSettings>) {
+	settings.invert_y = true;
+}
+
+fn disable_invert_y(_on: On<Pointer<Click>>, mut settings: ResMut<ControlSettings>) {
+	settings.invert_y = false;
+}
+
+fn update_invert_y_label(
+	mut label: Single<&mut Text, With<InvertYLabel>>,
+	settings: Res<ControlSettings>,
+) {
+	label.0 = if settings.invert_y {
+		"On".into()
+	} else {
+		"Off".into()
+	};
+}
+
+fn lower_gamepad_look_sensitivity(_on: On<Pointer<Click>>, mut settings: ResMut<ControlSettings>) {
+	const MIN_SENSITIVITY: f32 = 0.1;
+	settings.gamepad_look_sensitivity =
+		(settings.gamepad_look_sensitivity - 0.1).max(MIN_SENSITIVITY);
+}
+
+fn raise_gamepad_look_sensitivity(_on: On<Pointer<Click>>, mut settings: ResMut<ControlSettings>) {
+	const MAX_SENSITIVITY: f32 = 5.0;
+	settings.gamepad_look_sensitivity =
+		(settings.gamepad_look_sensitivity + 0.1).min(MAX_SENSITIVITY);
+}
+
+fn update_gamepad_look_sensitivity_label(
+	mut label: Single<&mut Text, With<GamepadLookSensitivityLabel>>,
+	settings: Res<ControlSettings>,
+) {
+	label.0 = format!("{:.1}", settings.gamepad_look_sensitivity);
+}
+
+fn reset_to_defaults(
+	_on: On<Pointer<Click>>,
+	mut settings: ResMut<ControlSettings>,
+	mut conflict_label: Single<&mut Text, With<ConflictLabel>>,
+	mut commands: Commands,
+) {
+	*settings = ControlSettings::default();
+	conflict_label.0.clear();
+	commands.remove_resource::<PendingRebind>();
+}
+
+fn go_back_on_click(_on: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+	next_menu.set(Menu::Settings);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+	next_menu.set(Menu::Settings);
+}
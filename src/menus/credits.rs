@@ -2,15 +2,13 @@
 
 use crate::ui_layout::RootWidget;
 use crate::{menus::Menu, theme::prelude::*};
-use bevy::{
-	ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
-};
+use bevy::{ecs::spawn::SpawnIter, prelude::*, ui::Val::*};
 
 pub(super) fn plugin(app: &mut App) {
 	app.add_systems(OnEnter(Menu::Credits), spawn_credits_menu);
 	app.add_systems(
 		Update,
-		go_back.run_if(in_state(Menu::Credits).and(input_just_pressed(KeyCode::Escape))),
+		go_back.run_if(in_state(Menu::Credits).and(action_just_started::<MenuBack>)),
 	);
 }
 
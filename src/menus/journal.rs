@@ -0,0 +1,227 @@
+//! The journal page of the pause menu, listing the current objective and everything completed so far.
+
+use std::time::Duration;
+
+use bevy::{
+	ecs::spawn::{SpawnIter, SpawnWith},
+	prelude::*,
+	ui::Val::*,
+};
+
+use crate::{
+	font::VARIABLE_FONT,
+	gameplay::{
+		level::CurrentLevel,
+		objectives::{
+			CurrentObjective, Objective, ObjectiveCompleted, ObjectiveFailed, OptionalObjective,
+			SubObjectiveOf, SubObjectives,
+			journal::ObjectiveJournal,
+			ui::{OBJECTIVE_COMPLETED_COLOR, OBJECTIVE_FAILED_COLOR, OBJECTIVE_OPTIONAL_COLOR},
+		},
+	},
+	menus::Menu,
+	props::logic_entity::ObjectiveEntity,
+	theme::{
+		palette::{HEADER_TEXT, LABEL_TEXT},
+		prelude::*,
+	},
+	ui_layout::RootWidget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+	app.add_systems(OnEnter(Menu::Journal), spawn_journal_menu);
+	app.add_systems(
+		Update,
+		go_back.run_if(in_state(Menu::Journal).and(action_just_started::<MenuBack>)),
+	);
+}
+
+fn spawn_journal_menu(
+	mut commands: Commands,
+	current_objective: Res<CurrentObjective>,
+	journal: Res<ObjectiveJournal>,
+	objectives: Query<(
+		&Objective,
+		&ObjectiveEntity,
+		Has<ObjectiveCompleted>,
+		Has<ObjectiveFailed>,
+		Has<OptionalObjective>,
+	)>,
+	sub_objectives: Query<&SubObjectives>,
+	parents: Query<&SubObjectiveOf>,
+) {
+	let current_rows: Vec<_> = (**current_objective)
+		.into_iter()
+		.flat_map(|objective| {
+			std::iter::once(objective).chain(sub_objectives.iter_descendants_depth_first(objective))
+		})
+		.filter_map(|entity| {
+			let (objective, objective_entity, completed, failed, optional) =
+				objectives.get(entity).ok()?;
+			let color = if failed {
+				OBJECTIVE_FAILED_COLOR
+			} else if completed {
+				OBJECTIVE_COMPLETED_COLOR
+			} else {
+				LABEL_TEXT
+			};
+			let description = if optional {
+				format!("{} (optional)", objective.description)
+			} else {
+				objective.description.clone()
+			};
+			Some(journal_row(
+				description,
+				objective_entity.objective_flavor.clone(),
+				parents.iter_ancestors(entity).count(),
+				color,
+				completed || failed,
+			))
+		})
+		.collect();
+	let current_rows = if current_rows.is_empty() {
+		vec![journal_row(
+			"Nothing to do right now",
+			None,
+			0,
+			OBJECTIVE_OPTIONAL_COLOR,
+			false,
+		)]
+	} else {
+		current_rows
+	};
+
+	let mut completed_rows = Vec::new();
+	let mut last_level = None;
+	for entry in &journal.entries {
+		if last_level != Some(entry.level) {
+			last_level = Some(entry.level);
+			completed_rows.push(journal_row(
+				level_name(entry.level),
+				None,
+				0,
+				HEADER_TEXT,
+				false,
+			));
+		}
+		completed_rows.push(journal_row(
+			format!(
+				"{}  {}",
+				format_playtime(entry.completed_at),
+				entry.description
+			),
+			entry.flavor.clone(),
+			entry.depth + 1,
+			OBJECTIVE_COMPLETED_COLOR,
+			false,
+		));
+	}
+	if completed_rows.is_empty() {
+		completed_rows.push(journal_row(
+			"Nothing yet",
+			None,
+			0,
+			OBJECTIVE_OPTIONAL_COLOR,
+			false,
+		));
+	}
+
+	commands.spawn((
+		RootWidget,
+		DespawnOnExit(Menu::Journal),
+		GlobalZIndex(3),
+		children![
+			widget::header("Journal"),
+			widget::label("Current objective"),
+			journal_section(current_rows),
+			widget::label(format!(
+				"Completed  (played {})",
+				format_playtime(journal.playtime)
+			)),
+			journal_section(completed_rows),
+			widget::button("Back", go_back_on_click),
+		],
+	));
+}
+
+fn journal_section(rows: Vec<impl Bundle>) -> impl Bundle {
+	(
+		Name::new("Journal Section"),
+		Node {
+			flex_direction: FlexDirection::Column,
+			row_gap: Px(4.0),
+			width: Px(600.0),
+			margin: UiRect::bottom(Px(10.0)),
+			..default()
+		},
+		Children::spawn(SpawnIter(rows.into_iter())),
+	)
+}
+
+/// A single objective in the journal, indented by its depth, with its flavor text underneath
+fn journal_row(
+	description: impl Into<String>,
+	flavor: Option<String>,
+	depth: usize,
+	color: Color,
+	struck: bool,
+) -> impl Bundle {
+	let description = description.into();
+	(
+		Name::new("Journal Row"),
+		Node {
+			flex_direction: FlexDirection::Column,
+			padding: UiRect::left(Px(depth as f32 * 20.0)),
+			..default()
+		},
+		Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+			let mut text = parent.spawn((
+				Text::new(description),
+				TextFont {
+					font: VARIABLE_FONT,
+					font_size: (18.0 - depth as f32 * 2.0).max(12.0),
+					weight: FontWeight(if depth == 0 { 800 } else { 500 }),
+					..default()
+				},
+				TextColor(color),
+			));
+			if struck {
+				text.insert((Strikethrough, StrikethroughColor(color)));
+			}
+			if let Some(flavor) = flavor {
+				parent
+					.spawn(widget::label_small(flavor))
+					.insert(TextColor(OBJECTIVE_OPTIONAL_COLOR));
+			}
+		})),
+	)
+}
+
+fn level_name(level: CurrentLevel) -> &'static str {
+	match level {
+		CurrentLevel::Shaders => "Loading",
+		CurrentLevel::DayOne => "Day One",
+		CurrentLevel::DayTwo => "Day Two",
+		CurrentLevel::Commune => "The Commune",
+		CurrentLevel::Karoline => "Karoline",
+	}
+}
+
+/// Formats a playtime as `m:ss`, or `h:mm:ss` past the hour
+fn format_playtime(playtime: Duration) -> String {
+	let seconds = playtime.as_secs();
+	let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+	if hours > 0 {
+		format!("{hours}:{minutes:02}:{seconds:02}")
+	} else {
+		format!("{minutes}:{seconds:02}")
+	}
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+	next_menu.set(Menu::Pause);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+	next_menu.set(Menu::Pause);
+}
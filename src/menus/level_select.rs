@@ -4,8 +4,7 @@
 //! can pull individual pieces into different contexts.
 
 use bevy::{
-	ecs::spawn::{SpawnIter, SpawnWith},
-	input::common_conditions::input_just_pressed,
+	ecs::spawn::SpawnWith,
 	prelude::*,
 	ui::Val::*,
 	window::{CursorGrabMode, CursorOptions},
@@ -14,7 +13,12 @@ use bevy::{
 use crate::{
 	menus::Menu,
 	screens::Screen,
-	theme::{interaction::InteractionPalette, palette::SCREEN_BACKGROUND, widget},
+	theme::{
+		interaction::InteractionPalette,
+		navigation::{Focusable, MenuBack, action_just_started},
+		palette::SCREEN_BACKGROUND,
+		widget,
+	},
 };
 
 const NEON_GREEN: Color = Color::srgb(0.0, 1.0, 0.0);
@@ -119,16 +123,11 @@ pub(super) fn plugin(app: &mut App) {
 	app.add_systems(OnExit(Menu::LevelSelect), cleanup_selected_level);
 	app.add_systems(
 		Update,
-		(
-			handle_level_click,
-			update_selection_visuals,
-			handle_enter_level,
-		)
-			.run_if(in_state(Menu::LevelSelect)),
+		update_selection_visuals.run_if(in_state(Menu::LevelSelect)),
 	);
 	app.add_systems(
 		Update,
-		go_back.run_if(in_state(Menu::LevelSelect).and(input_just_pressed(KeyCode::Escape))),
+		go_back.run_if(in_state(Menu::LevelSelect).and(action_just_started::<MenuBack>)),
 	);
 }
 
@@ -198,7 +197,13 @@ fn level_grid() -> impl Bundle {
 		},
 		BackgroundColor(GRID_BG),
 		BorderColor::from(Color::srgb(0.3, 0.3, 0.0)),
-		Children::spawn(SpawnIter((0..LEVELS.len()).map(level_square))),
+		Children::spawn(SpawnWith(|parent: &mut ChildSpawner| {
+			for idx in 0..LEVELS.len() {
+				parent
+					.spawn(level_square(idx))
+					.observe(select_level_on_click);
+			}
+		})),
 	)
 }
 
@@ -225,6 +230,7 @@ fn level_square(idx: usize) -> impl Bundle {
 	(
 		Name::new(format!("Level Square {}", idx)),
 		Button,
+		Focusable,
 		LevelSquare(idx),
 		SelectedBorder(idx),
 		BackgroundColor(bg),
@@ -385,7 +391,7 @@ fn bottom_buttons() -> impl Bundle {
 			..default()
 		},
 		Children::spawn(SpawnWith(|parent: &mut ChildSpawner| {
-			parent.spawn(enter_button());
+			parent.spawn(enter_button()).observe(enter_level_on_click);
 			parent.spawn(back_button()).observe(go_back_on_click);
 		})),
 	)
@@ -396,6 +402,7 @@ fn enter_button() -> impl Bundle {
 	(
 		Name::new("Enter Level Button"),
 		Button,
+		Focusable,
 		EnterLevelButton,
 		BackgroundColor(ENTER_BG),
 		InteractionPalette {
@@ -426,6 +433,7 @@ fn back_button() -> impl Bundle {
 	(
 		Name::new("Back Button"),
 		Button,
+		Focusable,
 		BackgroundColor(Color::srgb(0.5, 0.1, 0.1)),
 		InteractionPalette {
 			none: Color::srgb(0.5, 0.1, 0.1),
@@ -453,14 +461,13 @@ fn back_button() -> impl Bundle {
 
 // --- Systems ---
 
-fn handle_level_click(
-	interaction_query: Query<(&Interaction, &LevelSquare), Changed<Interaction>>,
+fn select_level_on_click(
+	click: On<Pointer<Click>>,
+	squares: Query<&LevelSquare>,
 	mut selected: ResMut<SelectedLevel>,
 ) {
-	for (interaction, square) in &interaction_query {
-		if *interaction == Interaction::Pressed {
-			selected.0 = square.0;
-		}
+	if let Ok(square) = squares.get(click.entity) {
+		selected.0 = square.0;
 	}
 }
 
@@ -550,22 +557,18 @@ fn update_selection_visuals(
 	}
 }
 
-fn handle_enter_level(
-	interaction_query: Query<&Interaction, (Changed<Interaction>, With<EnterLevelButton>)>,
+fn enter_level_on_click(
+	_: On<Pointer<Click>>,
 	selected: Res<SelectedLevel>,
 	mut next_screen: ResMut<NextState<Screen>>,
 	mut next_menu: ResMut<NextState<Menu>>,
 	mut cursor_options: Single<&mut CursorOptions>,
 ) {
-	for interaction in &interaction_query {
-		if *interaction == Interaction::Pressed {
-			let level = &LEVELS[selected.0];
-			if !level.locked {
-				next_screen.set(Screen::Loading);
-				next_menu.set(Menu::None);
-				cursor_options.grab_mode = CursorGrabMode::Locked;
-			}
-		}
+	let level = &LEVELS[selected.0];
+	if !level.locked {
+		next_screen.set(Screen::Loading);
+		next_menu.set(Menu::None);
+		cursor_options.grab_mode = CursorGrabMode::Locked;
 	}
 }
 
@@ -1,6 +1,8 @@
 //! The game's main screen states and transitions between them.
 
+pub(crate) mod controls;
 mod credits;
+mod journal;
 mod kaleidoscope_background;
 mod level_select;
 mod main;
@@ -13,7 +15,9 @@ pub(super) fn plugin(app: &mut App) {
 	app.init_state::<Menu>();
 
 	app.add_plugins((
+		controls::plugin,
 		credits::plugin,
+		journal::plugin,
 		level_select::plugin,
 		main::plugin,
 		settings::plugin,
@@ -32,5 +36,7 @@ pub(crate) enum Menu {
 	LevelSelect,
 	Credits,
 	Settings,
+	Controls,
 	Pause,
+	Journal,
 }
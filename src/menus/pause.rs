@@ -6,16 +6,19 @@ use crate::{
 	gameplay::{crosshair::CrosshairState, player::input::BlocksInput},
 	menus::Menu,
 	screens::Screen,
-	theme::widget,
+	theme::{
+		navigation::{MenuBack, action_just_started},
+		widget,
+	},
 	ui_layout::RootWidget,
 };
-use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
 	app.add_systems(OnEnter(Menu::Pause), spawn_pause_menu);
 	app.add_systems(
 		Update,
-		go_back.run_if(in_state(Menu::Pause).and(input_just_pressed(KeyCode::Escape))),
+		go_back.run_if(in_state(Menu::Pause).and(action_just_started::<MenuBack>)),
 	);
 }
 
@@ -43,6 +46,12 @@ fn spawn_pause_menu(
 		GlobalZIndex(3),
 		widget::button("Settings", open_settings_menu),
 	));
+	commands.spawn((
+		DespawnOnExit(Menu::Pause),
+		RootWidget,
+		GlobalZIndex(3),
+		widget::button("Journal", open_journal_menu),
+	));
 	commands.spawn((
 		DespawnOnExit(Menu::Pause),
 		RootWidget,
@@ -60,6 +69,10 @@ fn open_settings_menu(_on: On<Pointer<Click>>, mut next_menu: ResMut<NextState<M
 	next_menu.set(Menu::Settings);
 }
 
+fn open_journal_menu(_on: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+	next_menu.set(Menu::Journal);
+}
+
 fn close_pause_menu(
 	_on: On<Pointer<Click>>,
 	mut next_menu: ResMut<NextState<Menu>>,
@@ -6,7 +6,7 @@ use bevy::ecs::lifecycle::HookContext;
 use bevy::ecs::query::QueryFilter;
 use bevy::ecs::world::DeferredWorld;
 use bevy::window::PresentMode;
-use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+use bevy::{prelude::*, ui::Val::*};
 use bevy_ahoy::camera::CharacterControllerCameraOf;
 use bevy_seedling::prelude::*;
 
@@ -14,7 +14,13 @@ use crate::scatter::quality::QualitySetting;
 use crate::ui_layout::RootWidget;
 use crate::{
 	audio::{MusicPool, perceptual::PerceptualVolumeConverter},
-	gameplay::player::camera::WorldModelFov,
+	gameplay::{
+		dialogue_view::settings::DialogueSettings,
+		player::{
+			camera::WorldModelFov,
+			controls::{ControlSettings, save_control_settings},
+		},
+	},
 	menus::Menu,
 	screens::Screen,
 	theme::prelude::*,
@@ -24,9 +30,11 @@ pub(super) fn plugin(app: &mut App) {
 	app.init_resource::<VsyncSetting>()
 		.init_resource::<CamSensitivitySetting>();
 	app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
+	// The interaction highlight is stored with the controls
+	app.add_systems(OnExit(Menu::Settings), save_control_settings);
 	app.add_systems(
 		Update,
-		go_back.run_if(in_state(Menu::Settings).and(input_just_pressed(KeyCode::Escape))),
+		go_back.run_if(in_state(Menu::Settings).and(action_just_started::<MenuBack>)),
 	);
 
 	app.add_systems(
@@ -39,6 +47,9 @@ pub(super) fn plugin(app: &mut App) {
 			update_camera_fov_label,
 			update_vsync.run_if(resource_exists_and_changed::<VsyncSetting>),
 			update_vsync_label,
+			update_auto_advance_label,
+			update_text_speed_label,
+			update_highlight_label,
 		)
 			.run_if(in_state(Menu::Settings)),
 	)
@@ -132,6 +143,36 @@ fn spawn_settings_menu(mut commands: Commands) {
 						}
 					),
 					widget::plus_minus_bar(VsyncLabel, disable_vsync, enable_vsync),
+					// Dialogue
+					(
+						widget::label("Auto-Advance Dialogue"),
+						Node {
+							justify_self: JustifySelf::End,
+							..default()
+						}
+					),
+					widget::plus_minus_bar(
+						AutoAdvanceLabel,
+						disable_auto_advance,
+						enable_auto_advance
+					),
+					(
+						widget::label("Text Speed"),
+						Node {
+							justify_self: JustifySelf::End,
+							..default()
+						}
+					),
+					widget::plus_minus_bar(TextSpeedLabel, lower_text_speed, raise_text_speed),
+					// Interaction
+					(
+						widget::label("Highlight Interactables"),
+						Node {
+							justify_self: JustifySelf::End,
+							..default()
+						}
+					),
+					widget::plus_minus_bar(HighlightLabel, disable_highlight, enable_highlight),
 					(
 						widget::label("Quality"),
 						Node {
@@ -142,6 +183,7 @@ fn spawn_settings_menu(mut commands: Commands) {
 					QualitySettingsButton
 				],
 			),
+			widget::button("Controls", open_controls_menu),
 			widget::button("Back", go_back_on_click),
 		],
 	));
@@ -341,6 +383,75 @@ fn update_vsync_label(mut label: Single<&mut Text, With<VsyncLabel>>, setting: R
 	label.0 = if setting.0 { "On".into() } else { "Off".into() };
 }
 
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct AutoAdvanceLabel;
+
+fn enable_auto_advance(_on: On<Pointer<Click>>, mut settings: ResMut<DialogueSettings>) {
+	settings.auto_advance = true;
+}
+
+fn disable_auto_advance(_on: On<Pointer<Click>>, mut settings: ResMut<DialogueSettings>) {
+	settings.auto_advance = false;
+}
+
+fn update_auto_advance_label(
+	mut label: Single<&mut Text, With<AutoAdvanceLabel>>,
+	settings: Res<DialogueSettings>,
+) {
+	label.0 = if settings.auto_advance {
+		"On".into()
+	} else {
+		"Off".into()
+	};
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TextSpeedLabel;
+
+fn lower_text_speed(_on: On<Pointer<Click>>, mut settings: ResMut<DialogueSettings>) {
+	settings.text_speed = settings.text_speed.slower();
+}
+
+fn raise_text_speed(_on: On<Pointer<Click>>, mut settings: ResMut<DialogueSettings>) {
+	settings.text_speed = settings.text_speed.faster();
+}
+
+fn update_text_speed_label(
+	mut label: Single<&mut Text, With<TextSpeedLabel>>,
+	settings: Res<DialogueSettings>,
+) {
+	label.0 = format!("{:?}", settings.text_speed);
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct HighlightLabel;
+
+fn enable_highlight(_on: On<Pointer<Click>>, mut settings: ResMut<ControlSettings>) {
+	settings.highlight_interactables = true;
+}
+
+fn disable_highlight(_on: On<Pointer<Click>>, mut settings: ResMut<ControlSettings>) {
+	settings.highlight_interactables = false;
+}
+
+fn update_highlight_label(
+	mut label: Single<&mut Text, With<HighlightLabel>>,
+	settings: Res<ControlSettings>,
+) {
+	label.0 = if settings.highlight_interactables {
+		"On".into()
+	} else {
+		"Off".into()
+	};
+}
+
+fn open_controls_menu(_on: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+	next_menu.set(Menu::Controls);
+}
+
 fn go_back_on_click(
 	_on: On<Pointer<Click>>,
 	screen: Res<State<Screen>>,
@@ -1,7 +1,7 @@
 use crate::{
 	asset_tracking::LoadResource,
 	gameplay::{TargetName, core::EnvironmentTemperature, interaction::InteractEvent},
-	props::interactables::InteractableEntity,
+	props::{interactables::InteractableEntity, specific::crt_screen::CrtScreen},
 	third_party::{
 		avian3d::CollisionLayer,
 		bevy_trenchbroom::{GetTrenchbroomModelPath as _, LoadTrenchbroomModel as _},
@@ -83,7 +83,7 @@ pub(super) fn plugin(app: &mut App) {
 // office
 
 #[point_class(
-	base(TargetName, InteractableEntity, Transform, Visibility),
+	base(TargetName, InteractableEntity, CrtScreen, Transform, Visibility),
 	model("models/office/crt.gltf")
 )]
 #[component(on_add = Crt::on_add)]
@@ -5,7 +5,10 @@ use bevy::{
 
 use bevy_trenchbroom::prelude::*;
 
-use crate::{gameplay::stomach::EdibleProp, reflection::ReflAppExt};
+use crate::{
+	gameplay::stomach::{EdibleProp, effect::DigestEffect},
+	reflection::ReflAppExt,
+};
 
 pub(super) fn plugin(app: &mut App) {
 	app.register_dynamic_component::<InteractableEntity>();
@@ -13,18 +16,28 @@ pub(super) fn plugin(app: &mut App) {
 
 /// Trenchbroom component for designing entities that can be interacted with.
 #[derive(Default, Clone)]
-#[base_class]
+#[base_class(base(DigestEffect))]
 #[component(on_insert = InteractableEntity::on_insert)]
 #[component(immutable)]
 pub struct InteractableEntity {
-	/// Whether this entity should be
+	/// Whether this entity can be eaten with the eat button
 	pub is_edible: bool,
+	/// Whether an edible entity can also be used with the interact button. Entities that aren't edible can always be used.
+	/// The relays fire when the entity is used, or when it's eaten if it can't be used.
+	pub is_usable: bool,
 	/// Whether this entity should have a special line of text for being interacted with or it should be inferred from being edible.
+	/// Edible entities that can't be used show it for eating.
 	pub interaction_text_override: Option<String>,
+	/// A special line of text for eating this entity, for entities that can be used and eaten.
+	pub eat_text_override: Option<String>,
 	/// What objective, if any, should be completed by this name. Should be the `targetname` of said objective.
 	pub completes_subobjective: Option<String>,
 	/// What entity, if any, should additionally receive [`InteractEvent`](crate::gameplay::interaction::InteractEvent) when this one activates.
 	pub interaction_relay: Option<String>,
+	/// How many seconds the interact button has to be held before this activates. Activates on press if `0`.
+	pub hold_duration: f32,
+	/// What entity, if any, should receive [`InteractEvent`](crate::gameplay::interaction::InteractEvent) when holding is cancelled before it's done.
+	pub on_hold_cancelled: Option<String>,
 }
 
 #[expect(dead_code)]
@@ -49,8 +62,7 @@ impl InteractableEntity {
 		InteractableEntity {
 			is_edible: false,
 			interaction_text_override: Some(text),
-			completes_subobjective: None,
-			interaction_relay: None,
+			..default()
 		}
 	}
 
@@ -80,6 +92,32 @@ impl InteractableEntity {
 		self.is_edible
 	}
 
+	/// Whether this entity can be used with the interact button.
+	pub fn can_use(&self) -> bool {
+		self.is_usable || (!self.is_edible && self.is_active())
+	}
+
+	/// Whether eating this entity fires its relays, because it can't be used.
+	pub fn relays_on_eat(&self) -> bool {
+		self.is_edible && !self.is_usable
+	}
+
+	/// The text shown next to the interact button
+	pub fn get_use_text(&self) -> &str {
+		self.get_interaction_text_override().unwrap_or("Interact")
+	}
+
+	/// The text shown next to the eat button
+	pub fn get_eat_text(&self) -> &str {
+		self.eat_text_override
+			.as_deref()
+			.or(self
+				.interaction_text_override
+				.as_deref()
+				.filter(|_| !self.is_usable))
+			.unwrap_or("Eat")
+	}
+
 	pub fn get_interaction_text_override(&self) -> Option<&str> {
 		self.interaction_text_override.as_deref()
 	}
@@ -92,6 +130,15 @@ impl InteractableEntity {
 		self.interaction_relay.as_deref()
 	}
 
+	/// Whether the interact button has to be held for [`Self::hold_duration`] instead of just pressed.
+	pub fn needs_hold(&self) -> bool {
+		self.hold_duration > 0.0
+	}
+
+	pub fn get_on_hold_cancelled(&self) -> Option<&str> {
+		self.on_hold_cancelled.as_deref()
+	}
+
 	/// Adds an override text, if there was none previously and returns the new component
 	#[must_use]
 	pub fn add_override(&self, text: &str) -> Self {
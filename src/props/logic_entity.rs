@@ -8,7 +8,7 @@ use bevy::{
 
 use bevy_transform_interpolation::TranslationEasingState;
 use bevy_trenchbroom::prelude::*;
-use bevy_yarnspinner::prelude::{DialogueRunner, YarnValue};
+use bevy_yarnspinner::prelude::DialogueRunner;
 
 use crate::{
 	PostPhysicsAppSystems,
@@ -16,13 +16,15 @@ use crate::{
 		TargetName, TargetnameEntityIndex,
 		interaction::InteractEvent,
 		npc::Npc,
-		objectives::{Objective, SubObjectiveOf},
+		objectives::{Objective, SubObjectiveOf, condition::ObjectiveCondition},
 		player::Player,
 		scripting::ReflectionSystems,
+		yarn_variables::{YarnValueType, YarnVariables},
 	},
 	props::interactables::InteractableEntity,
 	reflection::ReflAppExt,
 	third_party::avian3d::CollisionLayer,
+	third_party::bevy_yarnspinner::{ScriptRunner, run_script_node},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -41,6 +43,7 @@ pub(super) fn plugin(app: &mut App) {
 		.add_observer(interact_timers)
 		.add_observer(uninitialise_objectives)
 		.add_observer(talk_ify_yarnnode)
+		.add_observer(run_script_yarn_node)
 		.add_observer(on_sensor_start)
 		.add_observer(on_sensor_end)
 		.add_observer(run_setter)
@@ -90,8 +93,8 @@ fn initialise_objectives(
 struct UnitialisedObjective;
 
 /// An entity describing the identity of an objective
-/// Activates (completes) on [`InteractEvent`]
-#[point_class(base(TargetName, Objective))]
+/// Activates (completes) on [`InteractEvent`], or when its [`ObjectiveCondition`] holds
+#[point_class(base(TargetName, Objective, ObjectiveCondition))]
 #[derive(Default)]
 pub(crate) struct ObjectiveEntity {
 	/// The objective, if any, that this is a subobjective of
@@ -99,6 +102,19 @@ pub(crate) struct ObjectiveEntity {
 	pub target: Option<String>,
 	/// The ordering of the objective, bigger = later
 	pub objective_order: f32,
+	/// targetname of the entity the objective marker points at
+	pub objective_location: Option<String>,
+	/// Optional objectives never block advancing to the next level
+	pub objective_optional: bool,
+	/// Seconds to complete the objective in once it is current, 0 for no limit
+	/// The objective fails when the countdown runs out
+	pub objective_time_limit: f32,
+	/// targetname of the entities to activate when the objective is completed
+	pub objective_on_complete: Option<String>,
+	/// targetname of the entities to activate when the objective fails
+	pub objective_on_fail: Option<String>,
+	/// Flavor text shown under the objective in the journal
+	pub objective_flavor: Option<String>,
 }
 
 /// An entity describing a dialogue node or a script
@@ -112,7 +128,8 @@ pub(crate) struct YarnNode {
 	/// Title of the yarn script that should be executed when this node is interacted with.
 	#[class(must_set)]
 	pub(crate) yarn_node: String,
-	/// Whether this node should avoid the restrictions placed upon dialogue. TODO!
+	/// Whether this node is a script that runs without opening the dialogue UI.
+	/// Script nodes run commands, set variables and wait on the [`ScriptRunner`], alongside any conversation.
 	pub(crate) is_non_dialogue: bool,
 }
 
@@ -127,16 +144,34 @@ impl Default for YarnNode {
 
 fn talk_ify_yarnnode(
 	on: On<Add, YarnNode>,
+	yarn_node_query: Query<&YarnNode>,
 	interactable_query: Query<&InteractableEntity>,
 	mut commands: Commands,
 ) {
-	if let Ok(interaction) = interactable_query.get(on.entity) {
+	if let Ok(interaction) = interactable_query.get(on.entity)
+		&& !yarn_node_query
+			.get(on.entity)
+			.is_ok_and(|node| node.is_non_dialogue)
+	{
 		commands
 			.entity(on.entity)
 			.insert(interaction.add_override("Talk"));
 	}
 }
 
+fn run_script_yarn_node(
+	trigger: On<InteractEvent>,
+	yarn_node_query: Query<&YarnNode>,
+	script_runner: Single<(&mut DialogueRunner, &mut ScriptRunner)>,
+) {
+	if let Ok(node) = yarn_node_query.get(trigger.0)
+		&& node.is_non_dialogue
+	{
+		let (mut runner, mut script) = script_runner.into_inner();
+		run_script_node(&node.yarn_node, &mut runner, &mut script);
+	}
+}
+
 /// An entity describing a timer which triggers [`InteractEvent`] after some time.
 /// Can also be used as a timed relay.
 /// Activates on [`InteractEvent`]
@@ -324,6 +359,8 @@ pub(crate) struct YarnSetter {
 	pub yarn_variable_to_set: String,
 	/// Value string of the property to set
 	pub yarn_value_to_set: String,
+	/// How to read [`Self::yarn_value_to_set`]
+	pub yarn_value_type: YarnValueType,
 }
 
 fn run_setter(
@@ -347,16 +384,13 @@ fn run_setter(
 fn run_yarn_setter(
 	trigger: On<InteractEvent>,
 	setter_query: Query<&YarnSetter>,
-	mut dialogue_runner: Single<&mut DialogueRunner>,
+	mut variables: ResMut<YarnVariables>,
 ) -> Result {
 	if let Ok(setter) = setter_query.get(trigger.0) {
-		dialogue_runner
-			.variable_storage_mut()
-			.set(
-				format!("${}", dbg!(&setter.yarn_variable_to_set)),
-				YarnValue::from(setter.yarn_value_to_set.clone()),
-			)
-			.unwrap();
+		let value = setter.yarn_value_type.parse(&setter.yarn_value_to_set)?;
+		variables
+			.0
+			.insert(format!("${}", setter.yarn_variable_to_set), value);
 	}
 	Ok(())
 }
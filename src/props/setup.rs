@@ -2,6 +2,7 @@
 //! A *dynamic* prop in the context of this file is a prop that is influenced by physics,
 //! while a *static* prop is unmovable terrain.
 
+use crate::gameplay::core::{Conductivity, TemperatureSensor};
 use crate::third_party::avian3d::CollisionLayer;
 use crate::third_party::bevy_trenchbroom::LoadTrenchbroomModel as _;
 use avian3d::prelude::*;
@@ -11,6 +12,11 @@ use bevy_trenchbroom::class::QuakeClass;
 
 pub(super) fn plugin(_app: &mut App) {}
 
+/// Props only sense what's right around them
+const PROP_SENSOR_RADIUS: f32 = 0.3;
+/// Props take longer than the player to heat up and cool down
+const PROP_CONDUCTIVITY: f32 = 0.3;
+
 pub(crate) fn setup_static_prop_with_convex_hull<T: QuakeClass>(
 	add: On<Add, T>,
 	asset_server: Res<AssetServer>,
@@ -71,6 +77,7 @@ pub(crate) fn dynamic_bundle<T: QuakeClass>(
 			.with_default_density(800.0),
 		RigidBody::Dynamic,
 		SceneRoot(model),
+		prop_temperature(),
 	)
 }
 
@@ -94,6 +101,23 @@ pub(crate) fn dynamic_bundle_heavy<T: QuakeClass>(
 			.with_default_density(10000.0),
 		RigidBody::Dynamic,
 		SceneRoot(model),
+		prop_temperature(),
+	)
+}
+
+/// Lets a dynamic prop heat up and cool down with its surroundings
+fn prop_temperature() -> impl Bundle {
+	(
+		Conductivity(PROP_CONDUCTIVITY),
+		children![(
+			Name::new("Prop Temperature Sensor"),
+			TemperatureSensor,
+			Collider::sphere(PROP_SENSOR_RADIUS),
+			CollisionLayers::new(
+				CollisionLayer::Sensor,
+				[CollisionLayer::Default, CollisionLayer::Prop]
+			),
+		)],
 	)
 }
 
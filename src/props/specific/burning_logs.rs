@@ -14,6 +14,7 @@ use crate::third_party::bevy_trenchbroom::GetTrenchbroomModelPath as _;
 use crate::{
 	PostPhysicsAppSystems,
 	audio::SpatialPool,
+	gameplay::core::EnvironmentTemperature,
 	props::{effects::disable_shadow_casting_on_instance_ready, setup::static_bundle},
 	screens::Screen,
 };
@@ -37,6 +38,7 @@ pub(super) fn plugin(app: &mut App) {
 	base(Transform, Visibility),
 	model("models/darkmod/fireplace/burntwood.gltf")
 )]
+#[require(EnvironmentTemperature(60.0))]
 pub(crate) struct BurningLogs;
 
 #[derive(Resource, Asset, Clone, TypePath)]
@@ -0,0 +1,465 @@
+//! Emote screens for CRT entities.
+//!
+//! Every [`Crt`](crate::props::generic::Crt) carries a [`CrtScreen`] that draws its expressions from a [`CrtEmoteSet`].
+//! Yarn addresses the screens by `targetname`, e.g. `<<crt_emote monitor_3 angry>>`.
+
+use crate::{
+	asset_tracking::LoadResource as _,
+	gameplay::{TargetnameEntityIndex, dialogue_view::typewriter::Typewriter},
+	third_party::bevy_yarnspinner::ConversationRunner,
+};
+use bevy::{
+	asset::{AssetLoader, LoadContext, io::Reader},
+	ecs::{lifecycle::HookContext, world::DeferredWorld},
+	gltf::GltfMaterialName,
+	platform::collections::HashMap,
+	prelude::*,
+	scene::SceneInstanceReady,
+};
+use bevy_trenchbroom::prelude::*;
+use bevy_yarnspinner::events::DialogueCompleted;
+use serde::Deserialize;
+use std::collections::VecDeque;
+
+pub(super) fn plugin(app: &mut App) {
+	app.init_asset::<CrtEmoteSet>()
+		.init_asset_loader::<CrtEmoteSetLoader>()
+		.load_asset::<CrtEmoteSet>(DEFAULT_EMOTE_SET)
+		.add_observer(setup_crt_screen_material)
+		.add_observer(clear_crt_screens)
+		.add_systems(
+			Update,
+			(run_crt_screen_commands, animate_crt_screens).chain(),
+		);
+}
+
+/// The emote set used by screens that do not specify their own
+const DEFAULT_EMOTE_SET: &str = "models/office/crt/llmanager.emotes.ron";
+
+/// The targetname of the screens `llmanager_emote` talks to
+const LLMANAGER_TARGETNAME: &str = "llmanager";
+
+/// Name of the material in the CRT model that the emotes get painted on
+const SCREEN_MATERIAL_NAME: &str = "glass";
+
+#[cfg(feature = "dev")]
+const FRAME_EXTENSION: &str = "png";
+#[cfg(feature = "release")]
+const FRAME_EXTENSION: &str = "ktx2";
+
+/// Trenchbroom component for entities that have a screen capable of showing emotes.
+#[base_class]
+#[component(on_add = CrtScreen::on_add)]
+#[derive(Clone)]
+pub(crate) struct CrtScreen {
+	/// Path to the `.emotes.ron` file this screen takes its emotes from.
+	pub(crate) emote_set: String,
+}
+
+impl Default for CrtScreen {
+	fn default() -> Self {
+		Self {
+			emote_set: DEFAULT_EMOTE_SET.into(),
+		}
+	}
+}
+
+impl CrtScreen {
+	fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+		if world.is_scene_world() {
+			return;
+		}
+		let Some(screen) = world.get::<CrtScreen>(ctx.entity) else {
+			return;
+		};
+		let emote_set = world.resource::<AssetServer>().load(&screen.emote_set);
+		world
+			.commands()
+			.entity(ctx.entity)
+			.insert(CrtScreenState::new(emote_set));
+	}
+}
+
+/// Runtime state of a [`CrtScreen`]
+#[derive(Component)]
+pub(crate) struct CrtScreenState {
+	emote_set: Handle<CrtEmoteSet>,
+	/// This screen's own copy of the glass material, so screens don't all show the same face
+	material: Option<Handle<StandardMaterial>>,
+	/// Deferred commands, waiting for the typewriter to catch up
+	commands: VecDeque<ScreenCommand>,
+	emote: Option<PlayingEmote>,
+	sequence: VecDeque<SequenceStep>,
+	sequence_timer: Option<Timer>,
+	/// The frame currently pasted on the material, used to avoid touching the material every frame
+	shown_frame: Option<AssetId<Image>>,
+}
+
+impl CrtScreenState {
+	fn new(emote_set: Handle<CrtEmoteSet>) -> Self {
+		Self {
+			emote_set,
+			material: None,
+			commands: VecDeque::new(),
+			emote: None,
+			sequence: VecDeque::new(),
+			sequence_timer: None,
+			shown_frame: None,
+		}
+	}
+
+	fn play_emote(&mut self, name: String) {
+		self.emote = Some(PlayingEmote {
+			name,
+			frame: 0,
+			elapsed: 0.0,
+		});
+	}
+
+	fn play_sequence(&mut self, steps: &[SequenceStep]) {
+		self.sequence = steps.iter().cloned().collect();
+		self.advance_sequence();
+	}
+
+	/// Starts the next step of the sequence, if there is one
+	fn advance_sequence(&mut self) {
+		self.sequence_timer = None;
+		if let Some(step) = self.sequence.pop_front() {
+			if step.duration > 0.0 {
+				self.sequence_timer = Some(Timer::from_seconds(step.duration, TimerMode::Once));
+			}
+			self.play_emote(step.emote);
+		}
+	}
+
+	fn clear(&mut self) {
+		self.commands.clear();
+		self.emote = None;
+		self.sequence.clear();
+		self.sequence_timer = None;
+	}
+}
+
+struct PlayingEmote {
+	name: String,
+	frame: usize,
+	elapsed: f32,
+}
+
+struct ScreenCommand {
+	action: ScreenAction,
+	delay_graphemes: usize,
+}
+
+#[derive(Clone)]
+enum ScreenAction {
+	Emote(String),
+	Sequence(String),
+}
+
+/// A collection of emotes and sequences of emotes, loaded from an `.emotes.ron` file
+#[derive(Asset, TypePath, Debug)]
+pub(crate) struct CrtEmoteSet {
+	emotes: HashMap<String, CrtEmote>,
+	sequences: HashMap<String, Vec<SequenceStep>>,
+}
+
+#[derive(Debug)]
+struct CrtEmote {
+	frames: Vec<Handle<Image>>,
+	frame_duration: f32,
+	looping: bool,
+}
+
+impl CrtEmote {
+	fn frame(&self, index: usize) -> Option<&Handle<Image>> {
+		self.frames.get(index)
+	}
+}
+
+/// One step of an emote sequence
+#[derive(Deserialize, Clone, Debug)]
+struct SequenceStep {
+	emote: String,
+	/// How long to hold this emote, in seconds. The last step may use `0.0` to hold it indefinitely.
+	#[serde(default)]
+	duration: f32,
+}
+
+/// The on-disk representation of a [`CrtEmoteSet`]
+#[derive(Deserialize)]
+struct CrtEmoteSetDefinition {
+	emotes: std::collections::HashMap<String, CrtEmoteDefinition>,
+	#[serde(default)]
+	sequences: std::collections::HashMap<String, Vec<SequenceStep>>,
+}
+
+#[derive(Deserialize)]
+struct CrtEmoteDefinition {
+	/// Image paths without an extension, it gets picked based on the build
+	frames: Vec<String>,
+	#[serde(default = "CrtEmoteDefinition::default_frame_duration")]
+	frame_duration: f32,
+	#[serde(default)]
+	looping: bool,
+}
+
+impl CrtEmoteDefinition {
+	fn default_frame_duration() -> f32 {
+		0.25
+	}
+}
+
+#[derive(Default, TypePath)]
+struct CrtEmoteSetLoader;
+
+impl AssetLoader for CrtEmoteSetLoader {
+	type Asset = CrtEmoteSet;
+	type Settings = ();
+	type Error = BevyError;
+
+	async fn load(
+		&self,
+		reader: &mut dyn Reader,
+		_settings: &Self::Settings,
+		load_context: &mut LoadContext<'_>,
+	) -> Result<Self::Asset, Self::Error> {
+		let mut bytes = Vec::new();
+		reader.read_to_end(&mut bytes).await?;
+		let definition: CrtEmoteSetDefinition = ron::de::from_bytes(&bytes)?;
+
+		let emotes = definition
+			.emotes
+			.into_iter()
+			.map(|(name, emote)| {
+				let frames = emote
+					.frames
+					.iter()
+					.map(|path| load_context.load(format!("{path}.{FRAME_EXTENSION}")))
+					.collect();
+				let emote = CrtEmote {
+					frames,
+					frame_duration: emote.frame_duration,
+					looping: emote.looping,
+				};
+				(name, emote)
+			})
+			.collect();
+
+		Ok(CrtEmoteSet {
+			emotes,
+			sequences: definition.sequences.into_iter().collect(),
+		})
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["emotes.ron"]
+	}
+}
+
+/// Call this system from yarnspinner to change the emote of every screen with the given targetname
+pub(crate) fn set_crt_emote(
+	In((target, emote, delay_graphemes)): In<(String, String, Option<usize>)>,
+	index: Res<TargetnameEntityIndex>,
+	screens: Query<&mut CrtScreenState>,
+) {
+	queue_screen_command(
+		&target,
+		ScreenAction::Emote(emote),
+		delay_graphemes,
+		&index,
+		screens,
+	);
+}
+
+/// Call this system from yarnspinner to play an emote sequence on every screen with the given targetname
+pub(crate) fn play_crt_sequence(
+	In((target, sequence, delay_graphemes)): In<(String, String, Option<usize>)>,
+	index: Res<TargetnameEntityIndex>,
+	screens: Query<&mut CrtScreenState>,
+) {
+	queue_screen_command(
+		&target,
+		ScreenAction::Sequence(sequence),
+		delay_graphemes,
+		&index,
+		screens,
+	);
+}
+
+/// Shorthand for `crt_emote llmanager`, kept around for the existing dialogue
+pub(crate) fn set_llmanager_emote(
+	In((emote, delay_graphemes)): In<(String, Option<usize>)>,
+	index: Res<TargetnameEntityIndex>,
+	screens: Query<&mut CrtScreenState>,
+) {
+	queue_screen_command(
+		LLMANAGER_TARGETNAME,
+		ScreenAction::Emote(emote),
+		delay_graphemes,
+		&index,
+		screens,
+	);
+}
+
+fn queue_screen_command(
+	target: &str,
+	action: ScreenAction,
+	delay_graphemes: Option<usize>,
+	index: &TargetnameEntityIndex,
+	mut screens: Query<&mut CrtScreenState>,
+) {
+	let delay_graphemes = delay_graphemes.unwrap_or_default();
+	let mut found = false;
+	for &entity in index.get_entity_by_targetname(target) {
+		if let Ok(mut screen) = screens.get_mut(entity) {
+			screen.commands.push_back(ScreenCommand {
+				action: action.clone(),
+				delay_graphemes,
+			});
+			found = true;
+		}
+	}
+	if !found {
+		warn!("No CRT screen with targetname {target}");
+	}
+}
+
+/// Gives every CRT its own copy of the screen material once its model is spawned
+fn setup_crt_screen_material(
+	ready: On<SceneInstanceReady>,
+	mut screens: Query<&mut CrtScreenState>,
+	children: Query<&Children>,
+	mesh_materials: Query<(&GltfMaterialName, &MeshMaterial3d<StandardMaterial>)>,
+	mut materials: ResMut<Assets<StandardMaterial>>,
+	mut commands: Commands,
+) {
+	let Ok(mut screen) = screens.get_mut(ready.entity) else {
+		return;
+	};
+	for child in children.iter_descendants(ready.entity) {
+		let Ok((name, material)) = mesh_materials.get(child) else {
+			continue;
+		};
+		if name.0 != SCREEN_MATERIAL_NAME {
+			continue;
+		}
+		let own_material = screen
+			.material
+			.get_or_insert_with(|| {
+				let copy = materials.get(&material.0).cloned().unwrap_or_default();
+				materials.add(copy)
+			})
+			.clone();
+		commands.entity(child).insert(MeshMaterial3d(own_material));
+	}
+	if screen.material.is_none() {
+		warn!("Could not find the {SCREEN_MATERIAL_NAME} material on a CRT screen");
+	}
+}
+
+fn run_crt_screen_commands(
+	typewriter: Option<Res<Typewriter>>,
+	emote_sets: Res<Assets<CrtEmoteSet>>,
+	mut screens: Query<&mut CrtScreenState>,
+) {
+	// Commands from script nodes have no line to wait for
+	let elapsed_graphemes =
+		typewriter.map_or(usize::MAX, |typewriter| typewriter.elapsed_graphemes());
+	for mut screen in &mut screens {
+		while let Some(first) = screen.commands.front()
+			&& elapsed_graphemes >= first.delay_graphemes
+		{
+			let Some(command) = screen.commands.pop_front() else {
+				break;
+			};
+			match command.action {
+				ScreenAction::Emote(name) => {
+					screen.sequence.clear();
+					screen.sequence_timer = None;
+					screen.play_emote(name);
+				}
+				ScreenAction::Sequence(name) => {
+					let steps = emote_sets
+						.get(&screen.emote_set)
+						.and_then(|set| set.sequences.get(&name));
+					if let Some(steps) = steps {
+						screen.play_sequence(steps);
+					} else {
+						warn!("Unknown CRT emote sequence {name}");
+					}
+				}
+			}
+		}
+	}
+}
+
+fn animate_crt_screens(
+	time: Res<Time>,
+	emote_sets: Res<Assets<CrtEmoteSet>>,
+	mut materials: ResMut<Assets<StandardMaterial>>,
+	mut screens: Query<&mut CrtScreenState>,
+) {
+	for mut screen in &mut screens {
+		let screen = &mut *screen;
+		if let Some(timer) = &mut screen.sequence_timer
+			&& timer.tick(time.delta()).is_finished()
+		{
+			screen.advance_sequence();
+		}
+
+		let emote_set = emote_sets.get(&screen.emote_set);
+		let frame = screen.emote.as_mut().and_then(|playing| {
+			// Unknown emotes, like "off", blank the screen
+			let emote = emote_set?.emotes.get(&playing.name)?;
+			playing.elapsed += time.delta_secs();
+			if emote.frame_duration > 0.0 {
+				while playing.elapsed >= emote.frame_duration {
+					playing.elapsed -= emote.frame_duration;
+					playing.frame += 1;
+				}
+			}
+			if emote.looping {
+				playing.frame %= emote.frames.len().max(1);
+			} else {
+				playing.frame = playing.frame.min(emote.frames.len().saturating_sub(1));
+			}
+			emote.frame(playing.frame)
+		});
+
+		if frame.map(Handle::id) == screen.shown_frame {
+			continue;
+		}
+		screen.shown_frame = frame.map(Handle::id);
+		let Some(material) = screen
+			.material
+			.as_ref()
+			.and_then(|handle| materials.get_mut(handle))
+		else {
+			continue;
+		};
+		// Paste the texture or clear it
+		material.emissive_texture = frame.cloned();
+		if frame.is_some() {
+			material.emissive = Color::WHITE.to_linear() * 20.0;
+		} else {
+			material.emissive = Color::BLACK.into();
+		}
+	}
+}
+
+/// Clears the emotes when dialog stops
+/// so the faces don't linger on every screen
+/// in a 5 mile radius
+fn clear_crt_screens(
+	completed: On<DialogueCompleted>,
+	conversation: Query<(), With<ConversationRunner>>,
+	mut screens: Query<&mut CrtScreenState>,
+) {
+	if !conversation.contains(completed.entity) {
+		return;
+	}
+	for mut screen in &mut screens {
+		screen.clear();
+	}
+}
@@ -5,8 +5,8 @@ use bevy::prelude::*;
 mod burning_logs;
 mod chair;
 mod crate_;
+pub mod crt_screen;
 mod door;
-pub mod intro_crt;
 mod lamp_plain;
 mod lamp_shaded;
 mod lamp_sitting;
@@ -19,7 +19,7 @@ pub(super) fn plugin(app: &mut App) {
 		chair::plugin,
 		crate_::plugin,
 		door::plugin,
-		intro_crt::plugin,
+		crt_screen::plugin,
 		lamp_sitting::plugin,
 		lamp_wall_electric::plugin,
 		lamp_shaded::plugin,
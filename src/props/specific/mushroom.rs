@@ -70,7 +70,7 @@ pub fn scattered_shroom(
 			is_edible: true,
 			interaction_text_override: Some("Take a bite".to_string()),
 			completes_subobjective: Some("leave".to_string()),
-			interaction_relay: None,
+			..default()
 		});
 	}
 }
@@ -3,7 +3,15 @@
 use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
 use bevy_fix_cursor_unlock_web::ForceUnlockCursor;
 
-use crate::{Pause, menus::Menu, screens::Screen};
+use crate::{
+	Pause,
+	menus::{
+		Menu,
+		controls::{PendingRebind, capture_rebind},
+	},
+	screens::Screen,
+	theme::navigation::{TogglePause, action_just_started},
+};
 
 pub(super) fn plugin(app: &mut App) {
 	// Toggle pause on key press.
@@ -11,15 +19,20 @@ pub(super) fn plugin(app: &mut App) {
 		Update,
 		(
 			(pause, spawn_pause_overlay, open_pause_menu).run_if(
-				in_state(Screen::Gameplay)
-					.and(in_state(Menu::None))
-					.and(input_just_pressed(KeyCode::KeyP).or(input_just_pressed(KeyCode::Escape))),
-			),
-			close_menu.run_if(
-				in_state(Screen::Gameplay)
-					.and(not(in_state(Menu::None)))
-					.and(input_just_pressed(KeyCode::KeyP)),
+				in_state(Screen::Gameplay).and(in_state(Menu::None)).and(
+					action_just_started::<TogglePause>.or(input_just_pressed(KeyCode::Escape)),
+				),
 			),
+			close_menu
+				.run_if(
+					in_state(Screen::Gameplay)
+						.and(not(in_state(Menu::None)))
+						.and(action_just_started::<TogglePause>)
+						// The pause key may be what the player wants to bind
+						.and(not(resource_exists::<PendingRebind>)),
+				)
+				// Sees the rebind before the key press finishes it
+				.before(capture_rebind),
 		),
 	);
 	app.add_systems(OnExit(Screen::Gameplay), (close_menu, unpause));
@@ -0,0 +1,147 @@
+//! Short names for the inputs bound to actions, so prompts can show the key or button to press.
+//!
+//! Prompts show the binding of whichever device the player used last, see [`LastInputDevice`].
+
+use bevy::{
+	ecs::system::SystemParam,
+	input::{InputSystems, mouse::AccumulatedMouseMotion},
+	prelude::*,
+};
+use bevy_enhanced_input::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+	app.init_resource::<LastInputDevice>();
+	app.add_systems(PreUpdate, detect_input_device.after(InputSystems));
+}
+
+/// The kind of device the player last gave input with
+#[derive(Resource, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub(crate) enum LastInputDevice {
+	#[default]
+	KeyboardMouse,
+	Gamepad,
+}
+
+impl LastInputDevice {
+	fn uses(self, binding: Binding) -> bool {
+		match binding {
+			Binding::Keyboard { .. }
+			| Binding::MouseButton { .. }
+			| Binding::MouseMotion { .. }
+			| Binding::MouseWheel { .. } => self == Self::KeyboardMouse,
+			Binding::GamepadButton(_) | Binding::GamepadAxis(_) => self == Self::Gamepad,
+			Binding::AnyKey => true,
+			Binding::None => false,
+		}
+	}
+}
+
+/// Looks up what to press for an action on the [`LastInputDevice`]
+#[derive(SystemParam)]
+pub(crate) struct ActionGlyphs<'w, 's> {
+	device: Res<'w, LastInputDevice>,
+	bindings: Query<'w, 's, &'static Bindings>,
+	binding: Query<'w, 's, &'static Binding>,
+}
+
+impl ActionGlyphs<'_, '_> {
+	/// The first binding of the action that belongs to the current device, e.g. `E`, `LMB` or `A`
+	pub(crate) fn glyph(&self, action: Entity) -> Option<String> {
+		let bindings = self.bindings.get(action).ok()?;
+		self.binding
+			.iter_many(bindings)
+			.copied()
+			.find(|binding| self.device.uses(*binding))
+			.map(binding_glyph)
+	}
+}
+
+/// A short name for a binding that fits on a keycap
+pub(crate) fn binding_glyph(binding: Binding) -> String {
+	let mod_keys = binding.mod_keys();
+	let glyph = match binding {
+		Binding::Keyboard { key, .. } => key_glyph(key),
+		Binding::MouseButton { button, .. } => match button {
+			MouseButton::Left => "LMB".to_string(),
+			MouseButton::Right => "RMB".to_string(),
+			MouseButton::Middle => "MMB".to_string(),
+			MouseButton::Back => "Mouse 4".to_string(),
+			MouseButton::Forward => "Mouse 5".to_string(),
+			MouseButton::Other(index) => format!("Mouse {index}"),
+		},
+		Binding::GamepadButton(button) => gamepad_button_glyph(button).to_string(),
+		binding => binding.to_string(),
+	};
+	if mod_keys.is_empty() {
+		glyph
+	} else {
+		format!("{mod_keys} + {glyph}")
+	}
+}
+
+fn key_glyph(key: KeyCode) -> String {
+	let name = format!("{key:?}");
+	// `KeyE` -> `E`, `Digit1` -> `1`, everything else keeps its name
+	name.strip_prefix("Key")
+		.or_else(|| name.strip_prefix("Digit"))
+		.map(str::to_string)
+		.unwrap_or_else(|| match key {
+			KeyCode::ControlLeft | KeyCode::ControlRight => "Ctrl".to_string(),
+			KeyCode::ShiftLeft | KeyCode::ShiftRight => "Shift".to_string(),
+			KeyCode::AltLeft | KeyCode::AltRight => "Alt".to_string(),
+			KeyCode::Escape => "Esc".to_string(),
+			_ => name.replace("Arrow", ""),
+		})
+}
+
+/// Names gamepad buttons after the Xbox layout, which most players know
+fn gamepad_button_glyph(button: GamepadButton) -> &'static str {
+	match button {
+		GamepadButton::South => "A",
+		GamepadButton::East => "B",
+		GamepadButton::West => "X",
+		GamepadButton::North => "Y",
+		GamepadButton::LeftTrigger => "LB",
+		GamepadButton::RightTrigger => "RB",
+		GamepadButton::LeftTrigger2 => "LT",
+		GamepadButton::RightTrigger2 => "RT",
+		GamepadButton::LeftThumb => "LS",
+		GamepadButton::RightThumb => "RS",
+		GamepadButton::Select => "Back",
+		GamepadButton::Start => "Start",
+		GamepadButton::Mode => "Guide",
+		GamepadButton::DPadUp => "D-Pad Up",
+		GamepadButton::DPadDown => "D-Pad Down",
+		GamepadButton::DPadLeft => "D-Pad Left",
+		GamepadButton::DPadRight => "D-Pad Right",
+		_ => "?",
+	}
+}
+
+fn detect_input_device(
+	keys: Res<ButtonInput<KeyCode>>,
+	mouse_buttons: Res<ButtonInput<MouseButton>>,
+	mouse_motion: Res<AccumulatedMouseMotion>,
+	gamepads: Query<&Gamepad>,
+	mut device: ResMut<LastInputDevice>,
+) {
+	let gamepad_used = gamepads.iter().any(|gamepad| {
+		gamepad.get_just_pressed().next().is_some()
+			|| gamepad.left_stick().length() > STICK_THRESHOLD
+			|| gamepad.right_stick().length() > STICK_THRESHOLD
+	});
+	let keyboard_mouse_used = keys.get_just_pressed().next().is_some()
+		|| mouse_buttons.get_just_pressed().next().is_some()
+		|| mouse_motion.delta.length() > MOUSE_MOTION_THRESHOLD;
+	if gamepad_used {
+		device.set_if_neq(LastInputDevice::Gamepad);
+	} else if keyboard_mouse_used {
+		device.set_if_neq(LastInputDevice::KeyboardMouse);
+	}
+}
+
+/// How far a stick has to be pushed to count as using the gamepad, so drift doesn't switch devices
+const STICK_THRESHOLD: f32 = 0.5;
+/// How many pixels the mouse has to move in a frame to count as using it, so a bumped desk doesn't switch devices
+const MOUSE_MOTION_THRESHOLD: f32 = 4.0;
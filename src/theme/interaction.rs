@@ -90,9 +90,9 @@ fn apply_button_interaction_palette(
 #[derive(Resource, Asset, Reflect, Clone)]
 pub(crate) struct InteractionAssets {
 	#[dependency]
-	hover: Handle<AudioSample>,
+	pub(crate) hover: Handle<AudioSample>,
 	#[dependency]
-	press: Handle<AudioSample>,
+	pub(crate) press: Handle<AudioSample>,
 }
 
 impl InteractionAssets {
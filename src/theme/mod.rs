@@ -3,18 +3,29 @@
 // Unused utilities may trigger this lints undesirably.
 #![allow(dead_code)]
 
+pub(crate) mod input_glyph;
 pub(crate) mod interaction;
+pub(crate) mod navigation;
 pub(crate) mod palette;
 pub(crate) mod textures;
 pub(crate) mod widget;
 
 #[allow(unused_imports)]
 pub(crate) mod prelude {
-	pub(crate) use super::{interaction::InteractionPalette, palette as ui_palette, widget};
+	pub(crate) use super::{
+		interaction::InteractionPalette,
+		navigation::{Focusable, MenuBack, action_just_started},
+		palette as ui_palette, widget,
+	};
 }
 
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-	app.add_plugins((interaction::plugin, textures::plugin));
+	app.add_plugins((
+		input_glyph::plugin,
+		interaction::plugin,
+		navigation::plugin,
+		textures::plugin,
+	));
 }
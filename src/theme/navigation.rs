@@ -0,0 +1,227 @@
+//! Keyboard and gamepad navigation for menus and dialogue options.
+//!
+//! Any UI entity with [`Focusable`] can be moved to with the arrow keys, D-pad or left stick.
+//! The entity that has [`Focused`] gets an outline, and confirming clicks it as if it was clicked with the mouse,
+//! so widgets only need to observe [`Pointer<Click>`] to work with every input device.
+
+use std::time::Duration;
+
+use bevy::{
+	camera::NormalizedRenderTarget,
+	picking::{
+		backend::HitData,
+		pointer::{Location, PointerButton, PointerId},
+	},
+	prelude::*,
+	window::{PrimaryWindow, WindowRef},
+};
+use bevy_enhanced_input::prelude::*;
+use bevy_seedling::sample::SamplePlayer;
+
+use crate::{audio::SfxPool, theme::interaction::InteractionAssets};
+
+pub(super) fn plugin(app: &mut App) {
+	app.add_input_context::<MenuInputContext>();
+	app.add_systems(Startup, setup_menu_input);
+	app.add_observer(navigate_focus)
+		.add_observer(confirm_focused)
+		.add_observer(focus_on_hover)
+		.add_observer(show_focus_outline)
+		.add_observer(hide_focus_outline);
+}
+
+/// Moves the focus between [`Focusable`] entities
+#[derive(Debug, InputAction)]
+#[action_output(Vec2)]
+pub(crate) struct NavigateMenu;
+
+/// Clicks the [`Focused`] entity
+#[derive(Debug, InputAction)]
+#[action_output(bool)]
+pub(crate) struct ConfirmMenu;
+
+/// Leaves the current menu
+#[derive(Debug, InputAction)]
+#[action_output(bool)]
+pub(crate) struct MenuBack;
+
+/// Opens or closes the pause menu
+#[derive(Debug, InputAction)]
+#[action_output(bool)]
+pub(crate) struct TogglePause;
+
+#[derive(Debug, Component, Default)]
+struct MenuInputContext;
+
+/// UI entities that can receive focus from keyboard and gamepad navigation.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub(crate) struct Focusable;
+
+/// The [`Focusable`] entity that is currently selected. There is at most one.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub(crate) struct Focused;
+
+fn setup_menu_input(mut commands: Commands) {
+	commands.spawn((
+		Name::new("MenuInput"),
+		MenuInputContext,
+		actions!(MenuInputContext[
+			(
+				Action::<NavigateMenu>::new(),
+				// The menu context is always active, so don't steal inputs from gameplay
+				ActionSettings { consume_input: false, ..default() },
+				DeadZone::default(),
+				Bindings::spawn((
+					Cardinal::arrows(),
+					Cardinal::dpad(),
+					Axial::left_stick(),
+				)),
+			),
+			(
+				Action::<ConfirmMenu>::new(),
+				ActionSettings { consume_input: false, ..default() },
+				bindings![KeyCode::Enter, GamepadButton::South],
+			),
+			(
+				Action::<MenuBack>::new(),
+				ActionSettings { consume_input: false, ..default() },
+				bindings![KeyCode::Escape, GamepadButton::East],
+			),
+			(
+				Action::<TogglePause>::new(),
+				ActionSettings { consume_input: false, ..default() },
+				bindings![KeyCode::KeyP, GamepadButton::Start],
+			),
+		]),
+	));
+}
+
+/// Run condition that is true on the frame the action `A` starts.
+pub(crate) fn action_just_started<A: InputAction>(
+	actions: Query<&ActionEvents, With<Action<A>>>,
+) -> bool {
+	actions
+		.iter()
+		.any(|events| events.contains(ActionEvents::START))
+}
+
+fn navigate_focus(
+	navigate: On<Start<NavigateMenu>>,
+	focusables: Query<(Entity, &UiGlobalTransform, &InheritedVisibility), With<Focusable>>,
+	focused: Query<Entity, With<Focused>>,
+	interaction_assets: Option<Res<InteractionAssets>>,
+	mut commands: Commands,
+) {
+	let candidates: Vec<(Entity, Vec2)> = focusables
+		.iter()
+		.filter(|(_, _, visibility)| visibility.get())
+		.map(|(entity, transform, _)| (entity, transform.translation))
+		.collect();
+
+	let current = focused.iter().find_map(|entity| {
+		candidates
+			.iter()
+			.find(|(candidate, _)| *candidate == entity)
+	});
+	let Some(&(current, current_position)) = current else {
+		// Nothing is focused yet, start at the top left
+		if let Some(&(first, _)) = candidates
+			.iter()
+			.min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+		{
+			focus(first, focused.iter(), &mut commands);
+		}
+		return;
+	};
+
+	// UI coordinates grow downwards
+	let direction = Vec2::new(navigate.value.x, -navigate.value.y).normalize_or_zero();
+	let next = candidates
+		.iter()
+		.filter(|(entity, _)| *entity != current)
+		.filter_map(|&(entity, position)| {
+			let offset = position - current_position;
+			let distance = offset.length();
+			if distance <= f32::EPSILON {
+				return None;
+			}
+			let alignment = offset.dot(direction) / distance;
+			// Prefer entities that are close and straight ahead
+			(alignment > 0.5).then_some((entity, distance * (2.0 - alignment)))
+		})
+		.min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+	if let Some((next, _)) = next {
+		focus(next, focused.iter(), &mut commands);
+		if let Some(interaction_assets) = interaction_assets {
+			commands.spawn((SamplePlayer::new(interaction_assets.hover.clone()), SfxPool));
+		}
+	}
+}
+
+fn focus(entity: Entity, previous: impl Iterator<Item = Entity>, commands: &mut Commands) {
+	for previous in previous {
+		commands.entity(previous).try_remove::<Focused>();
+	}
+	commands.entity(entity).insert(Focused);
+}
+
+/// Keeps the focus in sync with the mouse, so switching between devices feels natural
+fn focus_on_hover(
+	over: On<Pointer<Over>>,
+	focusables: Query<(), (With<Focusable>, Without<Focused>)>,
+	focused: Query<Entity, With<Focused>>,
+	mut commands: Commands,
+) {
+	if focusables.contains(over.entity) {
+		focus(over.entity, focused.iter(), &mut commands);
+	}
+}
+
+/// Sends a synthetic [`Pointer<Click>`] to the focused entity
+fn confirm_focused(
+	_confirm: On<Start<ConfirmMenu>>,
+	focused: Query<(Entity, &UiGlobalTransform, &InheritedVisibility), With<Focused>>,
+	window: Query<Entity, With<PrimaryWindow>>,
+	camera: Query<Entity, With<IsDefaultUiCamera>>,
+	interaction_assets: Option<Res<InteractionAssets>>,
+	mut commands: Commands,
+) {
+	let Some((entity, transform, _)) = focused.iter().find(|(_, _, visibility)| visibility.get())
+	else {
+		return;
+	};
+	let (Some(window), Some(camera)) = (
+		WindowRef::Primary.normalize(window.iter().next()),
+		camera.iter().next(),
+	) else {
+		return;
+	};
+	let location = Location {
+		target: NormalizedRenderTarget::Window(window),
+		position: transform.translation,
+	};
+	let click = Click {
+		button: PointerButton::Primary,
+		hit: HitData::new(camera, 0.0, None, None),
+		duration: Duration::ZERO,
+	};
+	commands.trigger(Pointer::new(PointerId::Mouse, location, click, entity));
+	if let Some(interaction_assets) = interaction_assets {
+		commands.spawn((SamplePlayer::new(interaction_assets.press.clone()), SfxPool));
+	}
+}
+
+fn show_focus_outline(add: On<Add, Focused>, mut commands: Commands) {
+	commands.entity(add.entity).insert(Outline {
+		width: Val::Px(3.0),
+		offset: Val::Px(2.0),
+		color: Color::WHITE,
+	});
+}
+
+fn hide_focus_outline(remove: On<Remove, Focused>, mut commands: Commands) {
+	commands.entity(remove.entity).try_remove::<Outline>();
+}
@@ -10,7 +10,10 @@ use bevy::{
 
 use crate::{
 	font::VARIABLE_FONT,
-	theme::{interaction::InteractionPalette, palette::*, textures::TexturedUiMaterial},
+	theme::{
+		interaction::InteractionPalette, navigation::Focusable, palette::*,
+		textures::TexturedUiMaterial,
+	},
 };
 
 /// A root UI node that fills the window and centers its content.
@@ -87,6 +90,29 @@ where
 			border: UiRect::all(px(4.0)),
 			..default()
 		},
+		40.0,
+	)
+}
+
+/// A wide button with smaller text than [`button`], for rows of options like key bindings.
+pub(crate) fn button_medium<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
+where
+	E: EntityEvent,
+	B: Bundle,
+	I: IntoObserverSystem<E, B, M>,
+{
+	button_base(
+		text,
+		action,
+		Node {
+			width: Px(200.0),
+			height: Px(36.0),
+			align_items: AlignItems::Center,
+			justify_content: JustifyContent::Center,
+			border_radius: BorderRadius::all(Px(8.0)),
+			..default()
+		},
+		20.0,
 	)
 }
 
@@ -107,6 +133,7 @@ where
 			justify_content: JustifyContent::Center,
 			..default()
 		},
+		40.0,
 	)
 }
 
@@ -115,6 +142,7 @@ fn button_base<E, B, M, I>(
 	text: impl Into<String>,
 	action: I,
 	button_bundle: impl Bundle,
+	font_size: f32,
 ) -> impl Bundle
 where
 	E: EntityEvent,
@@ -131,6 +159,7 @@ where
 				.spawn((
 					Name::new("Button Inner"),
 					Button,
+					Focusable,
 					BackgroundColor(BUTTON_BACKGROUND),
 					InteractionPalette {
 						none: BUTTON_BACKGROUND,
@@ -143,7 +172,7 @@ where
 					children![(
 						Name::new("Button Text"),
 						Text(text),
-						TextFont::from_font_size(40.0),
+						TextFont::from_font_size(font_size),
 						TextColor(BUTTON_TEXT),
 						// Don't bubble picking events from the text up to the button.
 						Pickable::IGNORE,
@@ -155,6 +184,37 @@ where
 	)
 }
 
+/// A small keycap showing the key or button bound to an action. Its text can be changed later through `text_marker`.
+pub(crate) fn keycap(text: impl Into<String>, text_marker: impl Component) -> impl Bundle {
+	(
+		Name::new("Keycap"),
+		Node {
+			min_width: Px(36.0),
+			height: Px(36.0),
+			padding: UiRect::horizontal(Px(8.0)),
+			align_items: AlignItems::Center,
+			justify_content: JustifyContent::Center,
+			border: UiRect::all(Px(2.0)),
+			border_radius: BorderRadius::all(Px(6.0)),
+			..default()
+		},
+		BorderColor::from(BUTTON_TEXT),
+		BackgroundColor(BUTTON_BACKGROUND),
+		children![(
+			Name::new("Keycap Text"),
+			Text(text.into()),
+			TextFont {
+				font: VARIABLE_FONT,
+				font_size: 20.0,
+				weight: FontWeight(800),
+				..default()
+			},
+			TextColor(BUTTON_TEXT),
+			text_marker,
+		)],
+	)
+}
+
 pub(crate) fn plus_minus_bar<E, B, M, I1, I2>(
 	label_marker: impl Component,
 	lower: I1,
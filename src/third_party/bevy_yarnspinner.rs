@@ -1,8 +1,13 @@
 //! [Yarnspinner](https://github.com/YarnSpinnerTool/YarnSpinner-Rust) handles dialogue.
 
+use std::collections::VecDeque;
+
 use bevy::prelude::*;
 
-use bevy_yarnspinner::{events::DialogueCompleted, prelude::*};
+use bevy_yarnspinner::{
+	events::{DialogueCompleted, PresentLine, PresentOptions},
+	prelude::*,
+};
 
 use crate::{
 	gameplay::{
@@ -14,8 +19,9 @@ use crate::{
 			despawn_entity, interact_with_entity, read_bool_from_entity, set_value_on_entity,
 			toggle_bool_on_entity,
 		},
+		yarn_variables::SharedYarnStorage,
 	},
-	props::specific::intro_crt::set_intro_crt_emote,
+	props::specific::crt_screen::{play_crt_sequence, set_crt_emote, set_llmanager_emote},
 	screens::Screen,
 };
 
@@ -36,14 +42,46 @@ pub(super) fn plugin(app: &mut App) {
 		}),
 	));
 	app.add_systems(OnEnter(Screen::Gameplay), setup_dialogue_runner);
+	app.add_observer(run_next_script)
+		.add_observer(skip_script_lines)
+		.add_observer(skip_script_options);
 	app.add_systems(
 		OnExit(Screen::Gameplay),
 		abort_all_dialogues_when_leaving_gameplay,
 	);
 }
 
-fn setup_dialogue_runner(mut commands: Commands, yarn_project: Res<YarnProject>) {
-	let mut dialogue_runner = yarn_project.create_dialogue_runner(&mut commands);
+fn setup_dialogue_runner(
+	mut commands: Commands,
+	yarn_project: Res<YarnProject>,
+	yarn_storage: Res<SharedYarnStorage>,
+) {
+	let conversation = build_dialogue_runner(&mut commands, &yarn_project, &yarn_storage);
+	let script = build_dialogue_runner(&mut commands, &yarn_project, &yarn_storage);
+	commands.spawn((
+		DespawnOnExit(Screen::Gameplay),
+		Name::new("Dialogue Runner"),
+		ConversationRunner,
+		conversation,
+	));
+	commands.spawn((
+		DespawnOnExit(Screen::Gameplay),
+		Name::new("Script Runner"),
+		ScriptRunner::default(),
+		script,
+	));
+}
+
+fn build_dialogue_runner(
+	commands: &mut Commands,
+	yarn_project: &YarnProject,
+	yarn_storage: &SharedYarnStorage,
+) -> DialogueRunner {
+	// Variables live outside the runner, so they survive it being despawned between levels
+	let mut dialogue_runner = yarn_project
+		.build_dialogue_runner(commands)
+		.with_variable_storage(yarn_storage.storage())
+		.build();
 	dialogue_runner
 		.commands_mut()
 		.add_command(
@@ -54,6 +92,18 @@ fn setup_dialogue_runner(mut commands: Commands, yarn_project: Res<YarnProject>)
 			"create_objective",
 			commands.register_system(create_dialogue_objective),
 		)
+		.add_command(
+			"create_optional_objective",
+			commands.register_system(create_dialogue_optional_objective),
+		)
+		.add_command(
+			"objective_flavor",
+			commands.register_system(set_dialogue_objective_flavor),
+		)
+		.add_command(
+			"fail_objective",
+			commands.register_system(fail_dialogue_objective),
+		)
 		.add_command(
 			"create_subobjective",
 			commands.register_system(create_dialogue_subobjective),
@@ -64,14 +114,17 @@ fn setup_dialogue_runner(mut commands: Commands, yarn_project: Res<YarnProject>)
 			"toggle_value",
 			commands.register_system(toggle_bool_on_entity),
 		)
+		.add_command("crt_emote", commands.register_system(set_crt_emote))
+		.add_command("crt_sequence", commands.register_system(play_crt_sequence))
 		.add_command(
 			"llmanager_emote",
-			commands.register_system(set_intro_crt_emote),
+			commands.register_system(set_llmanager_emote),
 		)
 		.add_command(
 			"interact_with",
 			commands.register_system(interact_with_entity),
-		);
+		)
+		.add_command("run_script", commands.register_system(run_script_command));
 	dialogue_runner
 		.library_mut()
 		.add_function(
@@ -82,11 +135,85 @@ fn setup_dialogue_runner(mut commands: Commands, yarn_project: Res<YarnProject>)
 			"is_bool_set",
 			commands.register_system(read_bool_from_entity),
 		);
-	commands.spawn((
-		DespawnOnExit(Screen::Gameplay),
-		Name::new("Dialogue Runner"),
-		dialogue_runner,
-	));
+	dialogue_runner
+}
+
+/// The runner for conversations, which are shown by the dialogue view.
+/// Only events from this runner open the dialogue UI.
+#[derive(Component, Debug, Default)]
+pub(crate) struct ConversationRunner;
+
+/// The runner for script nodes, which run commands, set variables and wait without any UI.
+///
+/// It runs alongside the [`ConversationRunner`], one script at a time. Scripts started while another one
+/// is running wait in line. Both runners share the [`SharedYarnStorage`], so neither owns a variable:
+/// every write is visible to the other runner right away, and when both write the same variable in the same frame
+/// the last write wins. Values from [`YarnVariables`](crate::gameplay::yarn_variables::YarnVariables) are applied
+/// before either runner updates.
+#[derive(Component, Debug, Default)]
+pub(crate) struct ScriptRunner {
+	queue: VecDeque<String>,
+}
+
+/// Runs a Yarn node on the [`ScriptRunner`], or queues it if a script is already running
+pub(crate) fn run_script_node(node: &str, runner: &mut DialogueRunner, script: &mut ScriptRunner) {
+	if runner.is_running() {
+		script.queue.push_back(node.to_string());
+	} else if let Err(err) = runner.try_start_node(node) {
+		warn!("Failed to run script node {node}: {err}");
+	}
+}
+
+fn run_script_command(
+	In(node): In<String>,
+	script_runner: Single<(&mut DialogueRunner, &mut ScriptRunner)>,
+) {
+	let (mut runner, mut script) = script_runner.into_inner();
+	run_script_node(&node, &mut runner, &mut script);
+}
+
+fn run_next_script(
+	completed: On<DialogueCompleted>,
+	mut script_runners: Query<(&mut DialogueRunner, &mut ScriptRunner)>,
+) {
+	let Ok((mut runner, mut script)) = script_runners.get_mut(completed.entity) else {
+		return;
+	};
+	while let Some(node) = script.queue.pop_front() {
+		match runner.try_start_node(&node) {
+			Ok(_) => return,
+			Err(err) => warn!("Failed to run script node {node}: {err}"),
+		}
+	}
+}
+
+/// Script nodes have nobody to show lines to, so skip past them
+fn skip_script_lines(
+	line: On<PresentLine>,
+	mut script_runners: Query<&mut DialogueRunner, With<ScriptRunner>>,
+) {
+	if let Ok(mut runner) = script_runners.get_mut(line.entity) {
+		warn!("Skipping line in script node: {}", line.line.text);
+		runner.continue_in_next_update();
+	}
+}
+
+/// Script nodes have nobody to choose options, so pick the first available one
+fn skip_script_options(
+	options: On<PresentOptions>,
+	mut script_runners: Query<&mut DialogueRunner, With<ScriptRunner>>,
+) -> Result {
+	let Ok(mut runner) = script_runners.get_mut(options.entity) else {
+		return Ok(());
+	};
+	let option = options
+		.options
+		.iter()
+		.find(|option| option.is_available)
+		.ok_or("Script node presented options, but none are available")?;
+	warn!("Picking option in script node: {}", option.line.text);
+	runner.select_option(option.id)?;
+	Ok(())
 }
 
 fn abort_all_dialogues_when_leaving_gameplay(